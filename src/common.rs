@@ -44,3 +44,204 @@ pub enum Which<P> {
 
 /// Set of `SymbolStyle`
 pub type SymbolStyles = BigEnumSet<SymbolStyle>;
+
+///
+/// RGB color triplet for truecolor workflows, see `rgb_from_ansi256` and `ansi256_from_rgb`
+///
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub struct Rgb {
+    /// red
+    pub r: u8,
+    /// green
+    pub g: u8,
+    /// blue
+    pub b: u8,
+}
+
+impl From<(u8, u8, u8)> for Rgb {
+    fn from(source: (u8, u8, u8)) -> Self {
+        Rgb {
+            r: source.0,
+            g: source.1,
+            b: source.2,
+        }
+    }
+}
+
+impl From<Rgb> for (u8, u8, u8) {
+    fn from(source: Rgb) -> Self {
+        (source.r, source.g, source.b)
+    }
+}
+
+/// Standard 16 system colors of the xterm palette
+const SYSTEM_COLORS: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (128, 0, 0),
+    (0, 128, 0),
+    (128, 128, 0),
+    (0, 0, 128),
+    (128, 0, 128),
+    (0, 128, 128),
+    (192, 192, 192),
+    (128, 128, 128),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (0, 0, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+///
+/// Converts xterm-256 palette *index* to `Rgb`.
+/// Consists of 16 system colors, 6x6x6 color cube and 24 step grayscale ramp.
+///
+pub fn rgb_from_ansi256(index: u8) -> Rgb {
+    let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+
+    match index {
+        0..=15 => Rgb::from(SYSTEM_COLORS[usize::from(index)]),
+        16..=231 => {
+            let i = index - 16;
+            Rgb {
+                r: level(i / 36),
+                g: level((i / 6) % 6),
+                b: level(i % 6),
+            }
+        }
+        _ => {
+            let gray = 8 + (index - 232) * 10;
+            Rgb {
+                r: gray,
+                g: gray,
+                b: gray,
+            }
+        }
+    }
+}
+
+/// Converts xterm-256 palette *index* to RGB triplet, see `rgb_from_ansi256`
+pub fn palette_to_rgb(index: u8) -> (u8, u8, u8) {
+    rgb_from_ansi256(index).into()
+}
+
+///
+/// Converts `Rgb` to the closest xterm-256 palette index.
+/// Picks the nearer of the 6x6x6 color cube and grayscale ramp entries,
+/// system colors are never returned as their actual RGB values vary by terminal.
+///
+pub fn ansi256_from_rgb(rgb: Rgb) -> u8 {
+    let cube = |v: u8| match v {
+        0..=47 => 0,
+        48..=114 => 1,
+        _ => (v - 35) / 40,
+    };
+    let distance = |index: u8| {
+        let other = rgb_from_ansi256(index);
+        let d = |a: u8, b: u8| (i32::from(a) - i32::from(b)).pow(2);
+
+        d(other.r, rgb.r) + d(other.g, rgb.g) + d(other.b, rgb.b)
+    };
+
+    let cube_index = 16 + 36 * cube(rgb.r) + 6 * cube(rgb.g) + cube(rgb.b);
+    let avg = (u16::from(rgb.r) + u16::from(rgb.g) + u16::from(rgb.b)) / 3;
+    let gray_index = 232 + std::cmp::min(avg.saturating_sub(3) / 10, 23) as u8;
+
+    if distance(gray_index) < distance(cube_index) {
+        gray_index
+    } else {
+        cube_index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn palette_to_rgb_known_indices() {
+        assert_eq!(palette_to_rgb(1), (128, 0, 0));
+        assert_eq!(palette_to_rgb(15), (255, 255, 255));
+        assert_eq!(palette_to_rgb(16), (0, 0, 0));
+        assert_eq!(palette_to_rgb(21), (0, 0, 255));
+        assert_eq!(palette_to_rgb(196), (255, 0, 0));
+        assert_eq!(palette_to_rgb(231), (255, 255, 255));
+    }
+
+    #[test]
+    fn palette_to_rgb_grayscale_ramp() {
+        assert_eq!(palette_to_rgb(232), (8, 8, 8));
+        assert_eq!(palette_to_rgb(244), (128, 128, 128));
+        assert_eq!(palette_to_rgb(255), (238, 238, 238));
+    }
+
+    #[test]
+    fn rgb_from_ansi256_known_pairs() {
+        assert_eq!(rgb_from_ansi256(16), Rgb { r: 0, g: 0, b: 0 });
+        assert_eq!(
+            rgb_from_ansi256(231),
+            Rgb {
+                r: 255,
+                g: 255,
+                b: 255
+            }
+        );
+        assert_eq!(
+            rgb_from_ansi256(67),
+            Rgb {
+                r: 95,
+                g: 135,
+                b: 175
+            }
+        );
+        assert_eq!(rgb_from_ansi256(232), Rgb { r: 8, g: 8, b: 8 });
+        assert_eq!(
+            rgb_from_ansi256(255),
+            Rgb {
+                r: 238,
+                g: 238,
+                b: 238
+            }
+        );
+    }
+
+    #[test]
+    fn ansi256_from_rgb_known_pairs() {
+        assert_eq!(ansi256_from_rgb(Rgb { r: 0, g: 0, b: 0 }), 16);
+        assert_eq!(
+            ansi256_from_rgb(Rgb {
+                r: 255,
+                g: 255,
+                b: 255
+            }),
+            231
+        );
+        assert_eq!(ansi256_from_rgb(Rgb { r: 8, g: 8, b: 8 }), 232);
+        assert_eq!(
+            ansi256_from_rgb(Rgb {
+                r: 238,
+                g: 238,
+                b: 238
+            }),
+            255
+        );
+        assert_eq!(
+            ansi256_from_rgb(Rgb {
+                r: 250,
+                g: 10,
+                b: 5
+            }),
+            196
+        );
+    }
+
+    #[test]
+    fn ansi256_round_trip_above_system_colors() {
+        for index in 16..=255u8 {
+            assert_eq!(ansi256_from_rgb(rgb_from_ansi256(index)), index);
+        }
+    }
+}
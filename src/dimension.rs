@@ -1,5 +1,6 @@
 use crate::{Position2D, Sprite};
-use std::convert::TryInto;
+use std::convert::{TryFrom, TryInto};
+use std::num::TryFromIntError;
 
 /// 2D dimension of up to 16bit size
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
@@ -19,9 +20,34 @@ impl From<(u16, u16)> for Dimension {
     }
 }
 
+impl TryFrom<(i32, i32)> for Dimension {
+    type Error = TryFromIntError;
+
+    /// Fails if either value is negative or does not fit into u16
+    fn try_from(source: (i32, i32)) -> Result<Self, Self::Error> {
+        Ok(Dimension {
+            w: u16::try_from(source.0)?,
+            h: u16::try_from(source.1)?,
+        })
+    }
+}
+
+impl std::ops::Add for Dimension {
+    type Output = Dimension;
+
+    /// Component-wise addition saturating at `u16::MAX`
+    fn add(self, other: Self) -> Self::Output {
+        Dimension {
+            w: self.w.saturating_add(other.w),
+            h: self.h.saturating_add(other.h),
+        }
+    }
+}
+
 impl std::ops::Sub for Dimension {
     type Output = Dimension;
 
+    /// Component-wise subtraction clamping at zero
     fn sub(self, other: Self) -> Self::Output {
         let w = if other.w > self.w {
             0
@@ -38,6 +64,18 @@ impl std::ops::Sub for Dimension {
     }
 }
 
+impl std::ops::Mul<u16> for Dimension {
+    type Output = Dimension;
+
+    /// Scales both width and height saturating at `u16::MAX`
+    fn mul(self, factor: u16) -> Self::Output {
+        Dimension {
+            w: self.w.saturating_mul(factor),
+            h: self.h.saturating_mul(factor),
+        }
+    }
+}
+
 impl Dimension {
     /// Single unit size dimension constructor (w = 1, h = 1)
     pub fn unit() -> Self {
@@ -49,6 +87,16 @@ impl Dimension {
         usize::from(self.w * self.h)
     }
 
+    /// Checks if width or height is zero
+    pub fn is_empty(self) -> bool {
+        self.w == 0 || self.h == 0
+    }
+
+    /// Checks if *other* fits within this dimension
+    pub fn contains(self, other: Dimension) -> bool {
+        other.w <= self.w && other.h <= self.h
+    }
+
     /// Calculates dimension between two 2D points, unit size for same point!
     pub fn for_area(top_left: Position2D, bottom_right: Position2D) -> Self {
         Dimension {
@@ -62,26 +110,203 @@ impl Dimension {
         Dimension { w, h }
     }
 
-    /// Calculates dimension for a `Sprite`
-    pub fn for_sprite(sprite: &Sprite) -> Self {
-        let mut w32 = 0i32;
-        let mut h32 = 0i32;
-
-        for t in sprite.all_iter() {
-            if t.pos.x > w32 {
-                w32 = t.pos.x;
-            }
-            if t.pos.y > h32 {
-                h32 = t.pos.y;
-            }
+    /// Swaps width and height, e.g. for 90 degree rotations
+    pub fn transpose(self) -> Self {
+        Dimension {
+            w: self.h,
+            h: self.w,
         }
+    }
 
-        w32 += 1;
-        h32 += 1;
+    /// Rounds each axis up to the next power of two. Zero maps to 1 as the smallest
+    /// power of two and values above 32768 are capped to it since 65536 doesn't fit u16
+    pub fn next_pow2(self) -> Self {
+        let pow2 = |v: u16| v.checked_next_power_of_two().unwrap_or(1 << 15);
 
         Dimension {
-            w: w32.try_into().unwrap_or(0),
-            h: h32.try_into().unwrap_or(0),
+            w: pow2(self.w),
+            h: pow2(self.h),
         }
     }
+
+    /// Calculates dimension for a `Sprite` from actual extents of texels in all
+    /// frames, zero size if empty
+    pub fn for_sprite(sprite: &Sprite) -> Self {
+        let mut texels = sprite.all_iter();
+        let first = match texels.next() {
+            Some(t) => t.pos,
+            None => return Dimension::default(),
+        };
+
+        let (min, max) = texels.fold((first, first), |(min, max), t| {
+            (
+                Position2D::from_xy(std::cmp::min(min.x, t.pos.x), std::cmp::min(min.y, t.pos.y)),
+                Position2D::from_xy(std::cmp::max(max.x, t.pos.x), std::cmp::max(max.y, t.pos.y)),
+            )
+        });
+
+        // extents can exceed i32 range, clamped to u16 max
+        let extent = |min: i32, max: i32| {
+            (i64::from(max) - i64::from(min) + 1)
+                .try_into()
+                .unwrap_or(u16::MAX)
+        };
+
+        Dimension {
+            w: extent(min.x, max.x),
+            h: extent(min.y, max.y),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::texels_from_str;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn for_sprite_offset_from_origin() {
+        let sprite = Sprite::from_texels(texels_from_str("ab\nc", Position2D::from_xy(5, 3)));
+
+        assert_eq!(Dimension::for_sprite(&sprite), Dimension::from_wh(2, 2));
+    }
+
+    #[test]
+    fn for_sprite_negative_coordinates() {
+        let sprite = Sprite::from_texels(texels_from_str("abc", Position2D::from_xy(-4, -2)));
+
+        assert_eq!(Dimension::for_sprite(&sprite), Dimension::from_wh(3, 1));
+    }
+
+    #[test]
+    fn for_sprite_extreme_coordinates() {
+        let mut texels = texels_from_str("a", Position2D::from_xy(i32::MIN, i32::MIN));
+        texels.extend(texels_from_str("b", Position2D::from_xy(i32::MAX - 1, 0)));
+        let sprite = Sprite::from_texels(texels);
+
+        assert_eq!(
+            Dimension::for_sprite(&sprite),
+            Dimension::from_wh(u16::MAX, u16::MAX)
+        );
+    }
+
+    #[test]
+    fn for_sprite_empty() {
+        assert_eq!(
+            Dimension::for_sprite(&Sprite::default()),
+            Dimension::default()
+        );
+    }
+
+    #[test]
+    fn next_pow2_rounds_up() {
+        assert_eq!(
+            Dimension::from_wh(3, 5).next_pow2(),
+            Dimension::from_wh(4, 8)
+        );
+    }
+
+    #[test]
+    fn next_pow2_keeps_powers_of_two() {
+        assert_eq!(
+            Dimension::from_wh(16, 1).next_pow2(),
+            Dimension::from_wh(16, 1)
+        );
+    }
+
+    #[test]
+    fn next_pow2_zero_maps_to_one() {
+        assert_eq!(
+            Dimension::from_wh(0, 2).next_pow2(),
+            Dimension::from_wh(1, 2)
+        );
+    }
+
+    #[test]
+    fn next_pow2_caps_large_values() {
+        assert_eq!(
+            Dimension::from_wh(40000, u16::MAX).next_pow2(),
+            Dimension::from_wh(32768, 32768)
+        );
+    }
+
+    #[test]
+    fn try_from_i32_valid() {
+        assert_eq!(
+            Dimension::try_from((3, 65535)),
+            Ok(Dimension::from_wh(3, 65535))
+        );
+        assert_eq!(Dimension::try_from((0, 0)), Ok(Dimension::default()));
+    }
+
+    #[test]
+    fn try_from_i32_negative() {
+        assert!(Dimension::try_from((-1, 2)).is_err());
+        assert!(Dimension::try_from((2, -1)).is_err());
+    }
+
+    #[test]
+    fn try_from_i32_overflow() {
+        assert!(Dimension::try_from((65536, 1)).is_err());
+        assert!(Dimension::try_from((1, i32::MAX)).is_err());
+    }
+
+    #[test]
+    fn add_component_wise() {
+        assert_eq!(
+            Dimension::from_wh(2, 3) + Dimension::from_wh(4, 5),
+            Dimension::from_wh(6, 8)
+        );
+        assert_eq!(
+            Dimension::from_wh(u16::MAX - 1, 1) + Dimension::from_wh(5, 1),
+            Dimension::from_wh(u16::MAX, 2)
+        );
+    }
+
+    #[test]
+    fn mul_saturates_near_ceiling() {
+        assert_eq!(Dimension::from_wh(3, 4) * 2, Dimension::from_wh(6, 8));
+        assert_eq!(
+            Dimension::from_wh(40000, 20000) * 2,
+            Dimension::from_wh(u16::MAX, 40000)
+        );
+    }
+
+    #[test]
+    fn is_empty_zero_sides() {
+        assert!(Dimension::from_wh(0, 3).is_empty());
+        assert!(Dimension::from_wh(3, 0).is_empty());
+        assert!(Dimension::default().is_empty());
+        assert!(!Dimension::unit().is_empty());
+    }
+
+    #[test]
+    fn contains_edge_cases() {
+        let dim = Dimension::from_wh(3, 2);
+
+        assert!(dim.contains(dim));
+        assert!(dim.contains(Dimension::from_wh(0, 0)));
+        assert!(dim.contains(Dimension::from_wh(1, 2)));
+        assert!(!dim.contains(Dimension::from_wh(4, 1)));
+        assert!(!dim.contains(Dimension::from_wh(1, 3)));
+    }
+
+    #[test]
+    fn transpose_swaps_and_inverts() {
+        let dim = Dimension::from_wh(3, 5);
+
+        assert_eq!(dim.transpose(), Dimension::from_wh(5, 3));
+        assert_eq!(dim.transpose().transpose(), dim);
+    }
+
+    #[test]
+    fn for_sprite_spans_all_frames() {
+        let mut sprite = Sprite::from_texels(texels_from_str("a", Position2D::from_xy(2, 2)));
+        sprite
+            .frames
+            .push(texels_from_str("bc", Position2D::from_xy(4, 1)));
+
+        assert_eq!(Dimension::for_sprite(&sprite), Dimension::from_wh(4, 2));
+    }
 }
@@ -1,4 +1,4 @@
-use specs::{Component, VecStorage};
+use specs::{Component, DenseVecStorage, VecStorage};
 
 impl Component for crate::Position {
     type Storage = VecStorage<Self>;
@@ -12,6 +12,50 @@ impl Component for crate::Dimension {
     type Storage = VecStorage<Self>;
 }
 
+// sprites are large and usually sparse among entities, dense storage
+// avoids reserving a full sprite sized slot for every entity
 impl Component for crate::Sprite {
+    type Storage = DenseVecStorage<Self>;
+}
+
+impl Component for crate::Bounds {
     type Storage = VecStorage<Self>;
 }
+
+impl Component for crate::Texel {
+    type Storage = VecStorage<Self>;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{texels_from_str, Bounds, Dimension, Position2D, Sprite, Texel};
+    use specs::{Builder, World, WorldExt};
+
+    #[test]
+    fn bounds_and_texel_register_with_world() {
+        let mut world = World::new();
+        world.register::<Bounds>();
+        world.register::<Texel>();
+
+        let area = Bounds::Free(Position2D::from_xy(1, 2), Dimension::from_wh(3, 4));
+        let texel = texels_from_str("x", Position2D::default()).remove(0);
+        let entity = world.create_entity().with(area).with(texel.clone()).build();
+
+        assert_eq!(world.read_storage::<Bounds>().get(entity), Some(&area));
+        assert_eq!(world.read_storage::<Texel>().get(entity), Some(&texel));
+    }
+
+    #[test]
+    fn sprite_round_trips_through_world() {
+        let mut world = World::new();
+        world.register::<Sprite>();
+
+        let sprite = Sprite::from_texels(texels_from_str("ab", Position2D::default()));
+        let with_sprite = world.create_entity().with(sprite.clone()).build();
+        let without = world.create_entity().build();
+
+        let storage = world.read_storage::<Sprite>();
+        assert_eq!(storage.get(with_sprite), Some(&sprite));
+        assert_eq!(storage.get(without), None);
+    }
+}
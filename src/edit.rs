@@ -0,0 +1,107 @@
+use crate::{Bounds, ColorMode, Position2D, Sprite, SymbolStyle, Texels, Which};
+
+///
+/// Describes a mutating `Sprite` operation so it can be recorded and replayed
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditOp {
+    /// See `Sprite::apply_symbol`, symbol with bg and fg colors in area
+    ApplySymbol(char, u8, u8, Bounds),
+    /// See `Sprite::apply_texels`, texels at position
+    ApplyTexels(Texels, Position2D),
+    /// See `Sprite::apply_color`
+    ApplyColor(ColorMode, u8, Bounds),
+    /// See `Sprite::apply_style`
+    ApplyStyle(SymbolStyle, Bounds),
+    /// See `Sprite::fill_color`
+    FillColor(ColorMode, u8),
+    /// See `Sprite::fill_style`
+    FillStyle(SymbolStyle),
+    /// See `Sprite::clear_symbol`
+    Clear(Bounds),
+    /// See `Sprite::new_frame`
+    NewFrame,
+    /// See `Sprite::delete_frame`
+    DeleteFrame,
+    /// See `Sprite::apply_frame_change`
+    FrameChange(Which<usize>),
+}
+
+impl Sprite {
+    /// Applies given `EditOp` to this sprite
+    pub fn apply_op(&mut self, op: EditOp) {
+        match op {
+            EditOp::ApplySymbol(symbol, bg, fg, area) => {
+                self.apply_symbol(symbol, bg, fg, area);
+            }
+            EditOp::ApplyTexels(texels, pos) => {
+                self.apply_texels(texels, pos);
+            }
+            EditOp::ApplyColor(cm, color, area) => {
+                self.apply_color(cm, color, area);
+            }
+            EditOp::ApplyStyle(style, area) => {
+                self.apply_style(style, area);
+            }
+            EditOp::FillColor(cm, color) => {
+                self.fill_color(cm, color);
+            }
+            EditOp::FillStyle(style) => {
+                self.fill_style(style);
+            }
+            EditOp::Clear(area) => {
+                self.clear_symbol(area);
+            }
+            EditOp::NewFrame => self.new_frame(),
+            EditOp::DeleteFrame => {
+                self.delete_frame();
+            }
+            EditOp::FrameChange(which) => {
+                self.apply_frame_change(which);
+            }
+        }
+    }
+
+    /// Applies given `EditOp` to this sprite and records it into *log*
+    pub fn record_op(&mut self, op: EditOp, log: &mut Vec<EditOp>) {
+        log.push(op.clone());
+        self.apply_op(op);
+    }
+
+    /// Replays all operations from *log* in order
+    pub fn replay(&mut self, log: &[EditOp]) {
+        for op in log {
+            self.apply_op(op.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{texels_from_str, Dimension};
+
+    #[test]
+    fn replay_reproduces_recorded_ops() {
+        let mut log = Vec::new();
+        let mut s = Sprite::default();
+        let area = Bounds::Free(Position2D::from_xy(0, 0), Dimension::from_wh(2, 2));
+
+        s.record_op(EditOp::ApplySymbol('x', 1, 2, area), &mut log);
+        s.record_op(
+            EditOp::ApplyTexels(
+                texels_from_str("yz", Position2D::default()),
+                Position2D::from_xy(1, 2),
+            ),
+            &mut log,
+        );
+        s.record_op(EditOp::ApplyColor(ColorMode::Fg, 5, area), &mut log);
+        s.record_op(EditOp::NewFrame, &mut log);
+        s.record_op(EditOp::FillStyle(SymbolStyle::Bold), &mut log);
+        assert_eq!(log.len(), 5);
+
+        let mut replayed = Sprite::default();
+        replayed.replay(&log);
+        assert_eq!(replayed, s);
+    }
+}
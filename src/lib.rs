@@ -1,5 +1,6 @@
 mod common;
 mod dimension;
+mod edit;
 mod position;
 mod scene;
 mod sprite;
@@ -7,6 +8,7 @@ mod texel;
 
 pub use common::*;
 pub use dimension::*;
+pub use edit::*;
 pub use position::*;
 pub use scene::*;
 pub use sprite::*;
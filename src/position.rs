@@ -4,7 +4,7 @@ use crate::Dimension;
 use serde::{Deserialize, Serialize};
 
 /// 3D position
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
 pub struct Position {
     pub x: i32,
@@ -13,7 +13,7 @@ pub struct Position {
 }
 
 /// 2D position
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
 pub struct Position2D {
     pub x: i32,
@@ -124,6 +124,135 @@ impl Bounds {
         self.position().y + i32::from(self.dimension().h) - 1
     }
 
+    /// Position of cell at given *row* and *col* relative to this area, None if outside
+    pub fn cell_at(&self, row: u16, col: u16) -> Option<Position2D> {
+        let dim = self.dimension();
+
+        if row >= dim.h || col >= dim.w {
+            return None;
+        }
+
+        Some(*self.position() + Position2D::from_xy(i32::from(col), i32::from(row)))
+    }
+
+    ///
+    /// Splits this area into four quadrants of the same bounds kind.
+    /// Odd dimension remainders go to the right and bottom quadrants.
+    ///
+    /// # Returns
+    ///
+    /// * `[Bounds; 4]` - top-left, top-right, bottom-left and bottom-right quadrants
+    ///
+    pub fn quadrants(&self) -> [Bounds; 4] {
+        let pos = *self.position();
+        let dim = *self.dimension();
+        let (left_w, top_h) = (dim.w / 2, dim.h / 2);
+        let (right_w, bottom_h) = (dim.w - left_w, dim.h - top_h);
+        let (mid_x, mid_y) = (pos.x + i32::from(left_w), pos.y + i32::from(top_h));
+        let make = |pos: Position2D, dim: Dimension| match self {
+            Bounds::Binding(_, _) => Bounds::Binding(pos, dim),
+            Bounds::Free(_, _) => Bounds::Free(pos, dim),
+        };
+
+        [
+            make(pos, Dimension::from_wh(left_w, top_h)),
+            make(
+                Position2D::from_xy(mid_x, pos.y),
+                Dimension::from_wh(right_w, top_h),
+            ),
+            make(
+                Position2D::from_xy(pos.x, mid_y),
+                Dimension::from_wh(left_w, bottom_h),
+            ),
+            make(
+                Position2D::from_xy(mid_x, mid_y),
+                Dimension::from_wh(right_w, bottom_h),
+            ),
+        ]
+    }
+
+    ///
+    /// Subtracts *other* area from this one splitting the remainder into rectangles
+    /// of the same bounds kind. Full width top and bottom pieces are followed by
+    /// left and right pieces between them, empty pieces are omitted.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<Bounds>` - up to four remaining pieces, empty if fully covered
+    ///
+    pub fn subtract(&self, other: &Bounds) -> Vec<Bounds> {
+        if self.dimension().is_empty() {
+            return Vec::new();
+        }
+
+        let (left, top) = (self.position().x, self.position().y);
+        let (right, bottom) = (self.right() + 1, self.bottom() + 1);
+        let cut_left = std::cmp::max(left, other.position().x);
+        let cut_top = std::cmp::max(top, other.position().y);
+        let cut_right = std::cmp::min(right, other.right() + 1);
+        let cut_bottom = std::cmp::min(bottom, other.bottom() + 1);
+
+        if cut_left >= cut_right || cut_top >= cut_bottom {
+            return vec![*self];
+        }
+
+        // edges are exclusive on the right and bottom
+        let make = |x0: i32, y0: i32, x1: i32, y1: i32| {
+            let pos = Position2D::from_xy(x0, y0);
+            let dim = Dimension::from_wh((x1 - x0) as u16, (y1 - y0) as u16);
+
+            match self {
+                Bounds::Binding(_, _) => Bounds::Binding(pos, dim),
+                Bounds::Free(_, _) => Bounds::Free(pos, dim),
+            }
+        };
+
+        [
+            make(left, top, right, cut_top),
+            make(left, cut_bottom, right, bottom),
+            make(left, cut_top, cut_left, cut_bottom),
+            make(cut_right, cut_top, right, cut_bottom),
+        ]
+        .iter()
+        .filter(|piece| !piece.dimension().is_empty())
+        .copied()
+        .collect()
+    }
+
+    /// Iterates over positions in this area in an inward clockwise spiral from top-left
+    pub fn spiral_iter(&self) -> impl Iterator<Item = Position2D> {
+        let dim = self.dimension();
+        // computed in usize as Bounds::size multiplies in u16
+        let mut result = Vec::with_capacity(usize::from(dim.w) * usize::from(dim.h));
+
+        if !dim.is_empty() {
+            let (mut left, mut top) = (self.position().x, self.position().y);
+            let (mut right, mut bottom) = (self.right(), self.bottom());
+
+            while left <= right && top <= bottom {
+                result.extend((left..=right).map(|x| Position2D::from_xy(x, top)));
+                result.extend((top + 1..=bottom).map(|y| Position2D::from_xy(right, y)));
+                if top < bottom {
+                    result.extend((left..right).rev().map(|x| Position2D::from_xy(x, bottom)));
+                }
+                if left < right {
+                    result.extend(
+                        (top + 1..bottom)
+                            .rev()
+                            .map(|y| Position2D::from_xy(left, y)),
+                    );
+                }
+
+                left += 1;
+                top += 1;
+                right -= 1;
+                bottom -= 1;
+            }
+        }
+
+        result.into_iter()
+    }
+
     /// Checks if given coordinates are inside this bounded area
     pub fn contains(&self, other: Position2D) -> bool {
         let pos = self.position();
@@ -155,7 +284,10 @@ impl Bounds {
 
 impl std::fmt::Display for Position {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if self.z != 0 {
+        if f.alternate() {
+            // labeled form always including z
+            write!(f, "x={} y={} z={}", self.x, self.y, self.z)
+        } else if self.z != 0 {
             write!(f, "{},{},{}", self.x, self.y, self.z)
         } else {
             write!(f, "{},{}", self.x, self.y)
@@ -360,6 +492,32 @@ impl Position2D {
         }
     }
 
+    ///
+    /// Classifies this position as a delta vector into the nearest `Direction`
+    /// by its dominant axis. Diagonal ties resolve to the horizontal direction.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Direction>` - None for zero vector
+    ///
+    pub fn nearest_direction(self) -> Option<Direction> {
+        if self.x == 0 && self.y == 0 {
+            None
+        } else if self.x.unsigned_abs() >= self.y.unsigned_abs() {
+            Some(if self.x < 0 {
+                Direction::Left
+            } else {
+                Direction::Right
+            })
+        } else {
+            Some(if self.y < 0 {
+                Direction::Top
+            } else {
+                Direction::Bottom
+            })
+        }
+    }
+
     /// Create bounds from two points
     pub fn area(self, other: Position2D) -> Bounds {
         let top_left = Position2D {
@@ -376,6 +534,18 @@ impl Position2D {
         Bounds::Binding(top_left, dim)
     }
 
+    ///
+    /// Calculates grid cell coordinates this position falls into for given *grid* cell size.
+    /// Rounds toward negative infinity so negative positions map to negative cells.
+    /// Panics if *grid* has zero width or height.
+    ///
+    pub fn cell(self, grid: Dimension) -> Position2D {
+        Position2D {
+            x: self.x.div_euclid(i32::from(grid.w)),
+            y: self.y.div_euclid(i32::from(grid.h)),
+        }
+    }
+
     /// Create the list of all positions in given area from point with given dimension
     pub fn area_texels(self, dim: Dimension) -> Vec<Position2D> {
         let mut result = Vec::with_capacity(dim.size());
@@ -401,6 +571,18 @@ pub enum Direction {
     Right,
 }
 
+impl Direction {
+    /// Relative `Translation` of *step* cells in this direction, z unchanged
+    pub fn to_translation(self, step: i32) -> Translation {
+        match self {
+            Direction::Left => Translation::Relative(-step, 0, 0),
+            Direction::Top => Translation::Relative(0, -step, 0),
+            Direction::Bottom => Translation::Relative(0, step, 0),
+            Direction::Right => Translation::Relative(step, 0, 0),
+        }
+    }
+}
+
 ///
 /// Describes the translation operation
 ///
@@ -422,6 +604,13 @@ impl Default for Translation {
     }
 }
 
+/// Translates all *positions* by *offset* in place
+pub fn translate_positions(positions: &mut [Position2D], offset: Position2D) {
+    for pos in positions.iter_mut() {
+        *pos += offset;
+    }
+}
+
 fn coords_from_index(index: usize, dim: Dimension) -> Option<Position2D> {
     let i = index as i32;
     let w = i32::from(dim.w);
@@ -433,3 +622,261 @@ fn coords_from_index(index: usize, dim: Dimension) -> Option<Position2D> {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cell_positive_positions() {
+        let grid = Dimension::from_wh(4, 3);
+
+        assert_eq!(
+            Position2D::from_xy(0, 0).cell(grid),
+            Position2D::from_xy(0, 0)
+        );
+        assert_eq!(
+            Position2D::from_xy(3, 2).cell(grid),
+            Position2D::from_xy(0, 0)
+        );
+        assert_eq!(
+            Position2D::from_xy(4, 7).cell(grid),
+            Position2D::from_xy(1, 2)
+        );
+    }
+
+    #[test]
+    fn cell_negative_positions_floor() {
+        let grid = Dimension::from_wh(4, 3);
+
+        assert_eq!(
+            Position2D::from_xy(-1, -1).cell(grid),
+            Position2D::from_xy(-1, -1)
+        );
+        assert_eq!(
+            Position2D::from_xy(-4, -3).cell(grid),
+            Position2D::from_xy(-1, -1)
+        );
+        assert_eq!(
+            Position2D::from_xy(-5, -4).cell(grid),
+            Position2D::from_xy(-2, -2)
+        );
+    }
+
+    #[test]
+    fn cell_at_in_range() {
+        let area = Bounds::Free(Position2D::from_xy(2, 3), Dimension::from_wh(4, 2));
+
+        assert_eq!(area.cell_at(0, 0), Some(Position2D::from_xy(2, 3)));
+        assert_eq!(area.cell_at(1, 3), Some(Position2D::from_xy(5, 4)));
+    }
+
+    #[test]
+    fn cell_at_out_of_range() {
+        let area = Bounds::Free(Position2D::from_xy(2, 3), Dimension::from_wh(4, 2));
+
+        assert_eq!(area.cell_at(2, 0), None);
+        assert_eq!(area.cell_at(0, 4), None);
+        assert_eq!(Bounds::empty().cell_at(0, 0), None);
+    }
+
+    #[test]
+    fn display_plain_omits_zero_z() {
+        assert_eq!(format!("{}", Position { x: 1, y: -2, z: 0 }), "1,-2");
+        assert_eq!(format!("{}", Position { x: 1, y: -2, z: 3 }), "1,-2,3");
+    }
+
+    #[test]
+    fn display_alternate_always_labels_z() {
+        let pos = Position { x: 1, y: -2, z: 0 };
+
+        assert_eq!(format!("{:#}", pos), "x=1 y=-2 z=0");
+        assert_ne!(format!("{:#}", pos), format!("{}", pos));
+        assert_eq!(
+            format!("{:#}", Position { x: 0, y: 0, z: 5 }),
+            "x=0 y=0 z=5"
+        );
+    }
+
+    #[test]
+    fn direction_to_translation() {
+        assert_eq!(
+            Direction::Left.to_translation(2),
+            Translation::Relative(-2, 0, 0)
+        );
+        assert_eq!(
+            Direction::Top.to_translation(2),
+            Translation::Relative(0, -2, 0)
+        );
+        assert_eq!(
+            Direction::Bottom.to_translation(2),
+            Translation::Relative(0, 2, 0)
+        );
+        assert_eq!(
+            Direction::Right.to_translation(2),
+            Translation::Relative(2, 0, 0)
+        );
+    }
+
+    #[test]
+    fn quadrants_even() {
+        let area = Bounds::Free(Position2D::from_xy(1, 1), Dimension::from_wh(4, 4));
+
+        assert_eq!(
+            area.quadrants(),
+            [
+                Bounds::Free(Position2D::from_xy(1, 1), Dimension::from_wh(2, 2)),
+                Bounds::Free(Position2D::from_xy(3, 1), Dimension::from_wh(2, 2)),
+                Bounds::Free(Position2D::from_xy(1, 3), Dimension::from_wh(2, 2)),
+                Bounds::Free(Position2D::from_xy(3, 3), Dimension::from_wh(2, 2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn quadrants_odd() {
+        let area = Bounds::Binding(Position2D::default(), Dimension::from_wh(3, 3));
+
+        assert_eq!(
+            area.quadrants(),
+            [
+                Bounds::Binding(Position2D::from_xy(0, 0), Dimension::from_wh(1, 1)),
+                Bounds::Binding(Position2D::from_xy(1, 0), Dimension::from_wh(2, 1)),
+                Bounds::Binding(Position2D::from_xy(0, 1), Dimension::from_wh(1, 2)),
+                Bounds::Binding(Position2D::from_xy(1, 1), Dimension::from_wh(2, 2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn nearest_direction_axis_aligned() {
+        assert_eq!(Position2D::from_xy(0, 0).nearest_direction(), None);
+        assert_eq!(
+            Position2D::from_xy(-3, 0).nearest_direction(),
+            Some(Direction::Left)
+        );
+        assert_eq!(
+            Position2D::from_xy(3, 0).nearest_direction(),
+            Some(Direction::Right)
+        );
+        assert_eq!(
+            Position2D::from_xy(0, -1).nearest_direction(),
+            Some(Direction::Top)
+        );
+        assert_eq!(
+            Position2D::from_xy(0, 1).nearest_direction(),
+            Some(Direction::Bottom)
+        );
+    }
+
+    #[test]
+    fn nearest_direction_diagonal() {
+        assert_eq!(
+            Position2D::from_xy(2, -5).nearest_direction(),
+            Some(Direction::Top)
+        );
+        assert_eq!(
+            Position2D::from_xy(-5, 2).nearest_direction(),
+            Some(Direction::Left)
+        );
+        // ties go to the horizontal axis
+        assert_eq!(
+            Position2D::from_xy(2, 2).nearest_direction(),
+            Some(Direction::Right)
+        );
+    }
+
+    #[test]
+    fn spiral_iter_3x3_order() {
+        let area = Bounds::Free(Position2D::default(), Dimension::from_wh(3, 3));
+        let order: Vec<(i32, i32)> = area.spiral_iter().map(|p| (p.x, p.y)).collect();
+
+        assert_eq!(
+            order,
+            vec![
+                (0, 0),
+                (1, 0),
+                (2, 0),
+                (2, 1),
+                (2, 2),
+                (1, 2),
+                (0, 2),
+                (0, 1),
+                (1, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn spiral_iter_covers_each_cell_once() {
+        let area = Bounds::Free(Position2D::from_xy(-2, 1), Dimension::from_wh(4, 3));
+        let mut cells: Vec<(i32, i32)> = area.spiral_iter().map(|p| (p.x, p.y)).collect();
+        let mut expected: Vec<(i32, i32)> = area.into_iter().map(|p| (p.x, p.y)).collect();
+        cells.sort_unstable();
+        expected.sort_unstable();
+
+        assert_eq!(cells, expected);
+        assert_eq!(Bounds::empty().spiral_iter().count(), 0);
+    }
+
+    #[test]
+    fn translate_positions_negative_offset() {
+        let mut positions = [
+            Position2D::from_xy(0, 0),
+            Position2D::from_xy(3, 1),
+            Position2D::from_xy(-1, 5),
+        ];
+
+        translate_positions(&mut positions, Position2D::from_xy(-2, -1));
+        assert_eq!(
+            positions,
+            [
+                Position2D::from_xy(-2, -1),
+                Position2D::from_xy(1, 0),
+                Position2D::from_xy(-3, 4),
+            ]
+        );
+    }
+
+    fn free(x: i32, y: i32, w: u16, h: u16) -> Bounds {
+        Bounds::Free(Position2D::from_xy(x, y), Dimension::from_wh(w, h))
+    }
+
+    fn total_size(pieces: &[Bounds]) -> usize {
+        pieces.iter().map(|b| b.size()).sum()
+    }
+
+    #[test]
+    fn subtract_central_hole() {
+        let pieces = free(0, 0, 4, 4).subtract(&free(1, 1, 2, 2));
+
+        assert_eq!(pieces.len(), 4);
+        assert_eq!(total_size(&pieces), 12);
+        assert!(pieces
+            .iter()
+            .all(|p| !p.contains(Position2D::from_xy(1, 1))));
+    }
+
+    #[test]
+    fn subtract_edge_overlap() {
+        let pieces = free(0, 0, 4, 4).subtract(&free(2, -1, 5, 6));
+
+        assert_eq!(pieces, vec![free(0, 0, 2, 4)]);
+
+        let corner = free(0, 0, 4, 4).subtract(&free(3, 3, 2, 2));
+        assert_eq!(corner.len(), 2);
+        assert_eq!(total_size(&corner), 15);
+    }
+
+    #[test]
+    fn subtract_no_overlap() {
+        let area = free(0, 0, 2, 2);
+
+        assert_eq!(area.subtract(&free(5, 5, 2, 2)), vec![area]);
+    }
+
+    #[test]
+    fn subtract_fully_covered() {
+        assert!(free(1, 1, 2, 2).subtract(&free(0, 0, 4, 4)).is_empty());
+    }
+}
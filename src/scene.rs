@@ -1,5 +1,8 @@
-use crate::{Position, Position2D, Sprite, SpriteV1};
-use std::collections::BTreeMap;
+use crate::{Bounds, Dimension, Position, Position2D, Sprite, SpriteV1, SpriteV2, Texel, Texels};
+use std::collections::{BTreeMap, BTreeSet};
+#[cfg(feature = "serde_support")]
+use std::convert::TryInto;
+use std::ops::Bound;
 
 #[cfg(feature = "serde_support")]
 use serde::{Deserialize, Serialize};
@@ -14,7 +17,7 @@ use serde::{Deserialize, Serialize};
 /// * bool (selected indicator) -- *DEPRECATED!*
 ///
 /// #### WARNING
-/// The selected indicator boolean will get removed in V2
+/// The selected indicator boolean is dropped when converting to V2
 /// as the only "lossy" change of the Scene format.
 /// Leaving this in SceneV1 definition was a mistake during initial refactoring.
 #[derive(Debug, Default, Clone)]
@@ -24,28 +27,31 @@ pub struct SceneV1 {
 }
 
 ///
-/// Current scene version == V2
+/// Previous scene version == V2
 ///
 /// ### Contents
 /// SceneV2 consists of a list of tuples each having:
-/// * Sprite
+/// * SpriteV2
 /// * Position (for sprite)
 /// SceneV2 also consists of a list of:
 /// * Position2D (for bookmarks)
 ///
+/// #### NOTE
+/// SceneV2 does not contain the deprecated selected indicator of `SceneV1`.
+///
 #[derive(Debug, Default, Clone)]
 #[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
 pub struct SceneV2 {
-    pub objects: Vec<(Sprite, Position)>,
+    pub objects: Vec<(SpriteV2, Position)>,
     pub bookmarks: BTreeMap<usize, Position2D>,
 }
 
 impl From<SceneV1> for SceneV2 {
     fn from(older: SceneV1) -> Self {
-        let mut objects: Vec<(Sprite, Position)> = Vec::with_capacity(older.objects.capacity());
+        let mut objects: Vec<(SpriteV2, Position)> = Vec::with_capacity(older.objects.capacity());
 
         for obj in older.objects {
-            objects.push((Sprite::from(obj.0), obj.1))
+            objects.push((SpriteV2::from(obj.0), obj.1))
         }
 
         SceneV2 {
@@ -55,6 +61,339 @@ impl From<SceneV1> for SceneV2 {
     }
 }
 
+///
+/// Current scene version == V3
+///
+/// ### Contents
+/// SceneV3 consists of a list of tuples each having:
+/// * Sprite (with per frame names and durations)
+/// * Position (for sprite)
+///
+/// SceneV3 also consists of a list of:
+/// * Position2D (for bookmarks)
+///
+/// #### NOTE
+/// Converting from `SceneV2` is lossless, sprites get empty frame metadata.
+///
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub struct SceneV3 {
+    pub objects: Vec<(Sprite, Position)>,
+    pub bookmarks: BTreeMap<usize, Position2D>,
+}
+
+impl From<SceneV2> for SceneV3 {
+    fn from(older: SceneV2) -> Self {
+        let mut objects: Vec<(Sprite, Position)> = Vec::with_capacity(older.objects.capacity());
+
+        for obj in older.objects {
+            objects.push((Sprite::from(obj.0), obj.1))
+        }
+
+        SceneV3 {
+            objects,
+            bookmarks: older.bookmarks,
+        }
+    }
+}
+
+impl SceneV3 {
+    ///
+    /// Adds *sprite* at *pos* to the scene assigning it a unique id.
+    /// Sprite's own id is kept if set and not used in the scene already,
+    /// otherwise highest existing id + 1 is assigned.
+    ///
+    /// # Returns
+    ///
+    /// * `u32` - assigned sprite id
+    ///
+    pub fn add_sprite(&mut self, mut sprite: Sprite, pos: Position) -> u32 {
+        let id = match sprite.id {
+            Some(id) if self.sprite_by_id(id).is_none() => id,
+            _ => self
+                .objects
+                .iter()
+                .filter_map(|(sprite, _)| sprite.id)
+                .max()
+                .map_or(0, |max| max + 1),
+        };
+
+        sprite.id = Some(id);
+        self.objects.push((sprite, pos));
+
+        id
+    }
+
+    /// Removes object with sprite of given *id* returning it
+    pub fn remove_sprite(&mut self, id: u32) -> Option<(Sprite, Position)> {
+        let index = self
+            .objects
+            .iter()
+            .position(|(sprite, _)| sprite.id == Some(id))?;
+
+        Some(self.objects.remove(index))
+    }
+
+    /// Finds object with sprite of given *id*, first match if ids are duplicated
+    pub fn sprite_by_id(&self, id: u32) -> Option<&(Sprite, Position)> {
+        self.objects
+            .iter()
+            .find(|(sprite, _)| sprite.id == Some(id))
+    }
+
+    /// Finds mutable object with sprite of given *id*, first match if ids are duplicated
+    pub fn sprite_by_id_mut(&mut self, id: u32) -> Option<&mut (Sprite, Position)> {
+        self.objects
+            .iter_mut()
+            .find(|(sprite, _)| sprite.id == Some(id))
+    }
+
+    /// Finds object with sprite of given *id*, alias of `sprite_by_id`
+    pub fn find_by_id(&self, id: u32) -> Option<&(Sprite, Position)> {
+        self.sprite_by_id(id)
+    }
+
+    /// Finds mutable object with sprite of given *id*, alias of `sprite_by_id_mut`
+    pub fn find_by_id_mut(&mut self, id: u32) -> Option<&mut (Sprite, Position)> {
+        self.sprite_by_id_mut(id)
+    }
+
+    /// Removes object with sprite of given *id* returning it, alias of `remove_sprite`
+    pub fn remove_by_id(&mut self, id: u32) -> Option<(Sprite, Position)> {
+        self.remove_sprite(id)
+    }
+
+    /// Iterates over all objects whose sprite has given *label*, exact matches only.
+    /// An empty *label* only matches sprites explicitly labeled with an empty string
+    pub fn sprites_with_label<'a>(
+        &'a self,
+        label: &'a str,
+    ) -> impl Iterator<Item = &'a (Sprite, Position)> {
+        self.objects
+            .iter()
+            .filter(move |(sprite, _)| sprite.has_label(label))
+    }
+
+    /// Iterates mutably over all objects whose sprite has given *label*, see `sprites_with_label`
+    pub fn sprites_with_label_mut<'a>(
+        &'a mut self,
+        label: &'a str,
+    ) -> impl Iterator<Item = &'a mut (Sprite, Position)> {
+        self.objects
+            .iter_mut()
+            .filter(move |(sprite, _)| sprite.has_label(label))
+    }
+
+    /// Iterates over all objects whose position lies within *area*
+    pub fn sprites_in(&self, area: Bounds) -> impl Iterator<Item = &(Sprite, Position)> {
+        self.objects
+            .iter()
+            .filter(move |(_, pos)| area.contains(pos.into()))
+    }
+
+    /// Iterates over all non-empty objects whose sprite area overlaps *area*
+    pub fn sprites_overlapping(&self, area: Bounds) -> impl Iterator<Item = &(Sprite, Position)> {
+        self.objects.iter().filter(move |(sprite, pos)| {
+            let bounds = world_bounds(sprite, pos);
+
+            !sprite.is_empty() && area.intersects(*bounds.position(), *bounds.dimension())
+        })
+    }
+
+    /// References to all objects sorted by ascending z, ties keep insertion order
+    pub fn z_order_sorted_objects(&self) -> Vec<&(Sprite, Position)> {
+        let mut sorted: Vec<&(Sprite, Position)> = self.objects.iter().collect();
+        sorted.sort_by_key(|(_, pos)| pos.z);
+
+        sorted
+    }
+
+    ///
+    /// Composites active frames of all sprites into a single layer in absolute
+    /// positions. Sprites with higher z overwrite lower ones.
+    ///
+    /// # Returns
+    ///
+    /// * `Texels` - flattened texels ordered by rows
+    ///
+    pub fn flatten(&self) -> Texels {
+        let mut layer = BTreeMap::new();
+        for (sprite, pos) in self.z_order_sorted_objects() {
+            for texel in sprite.frame_iter() {
+                let mut placed = texel.clone();
+                placed.pos += Position2D::from(pos);
+                layer.insert((placed.pos.y, placed.pos.x), placed);
+            }
+        }
+
+        layer.into_values().collect()
+    }
+
+    ///
+    /// Renders active frames of all sprites into a row-major grid covering *viewport*.
+    /// Each cell holds the topmost non-transparent texel at that world position
+    /// according to z order, texels outside of *viewport* are clipped.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<Vec<Option<Texel>>>` - rows of cells with texels in world positions
+    ///
+    pub fn render_to_grid(&self, viewport: Bounds) -> Vec<Vec<Option<Texel>>> {
+        let dim = viewport.dimension();
+        let origin = *viewport.position();
+        let mut grid = vec![vec![None; usize::from(dim.w)]; usize::from(dim.h)];
+
+        for (sprite, pos) in self.z_order_sorted_objects() {
+            for texel in sprite.frame_iter().filter(|t| !t.is_transparent()) {
+                let mut placed = texel.clone();
+                placed.pos += Position2D::from(pos);
+
+                if viewport.contains(placed.pos) {
+                    let cell = placed.pos - origin;
+                    grid[cell.y as usize][cell.x as usize] = Some(placed);
+                }
+            }
+        }
+
+        grid
+    }
+
+    /// Union of areas covered by all non-empty sprites, empty bounds for empty scene
+    pub fn bounding_box(&self) -> Bounds {
+        let mut areas = self
+            .objects
+            .iter()
+            .filter(|(sprite, _)| !sprite.is_empty())
+            .map(|(sprite, pos)| world_bounds(sprite, pos));
+
+        let first = match areas.next() {
+            Some(area) => area,
+            None => return Bounds::empty(),
+        };
+
+        let (min, max) = areas.fold(
+            (
+                *first.position(),
+                Position2D::from_xy(first.right(), first.bottom()),
+            ),
+            |(min, max), area| {
+                (
+                    Position2D::from_xy(
+                        std::cmp::min(min.x, area.position().x),
+                        std::cmp::min(min.y, area.position().y),
+                    ),
+                    Position2D::from_xy(
+                        std::cmp::max(max.x, area.right()),
+                        std::cmp::max(max.y, area.bottom()),
+                    ),
+                )
+            },
+        );
+
+        Bounds::Free(min, Dimension::for_area(min, max))
+    }
+
+    ///
+    /// Combines this scene with *other* appending its objects. Bookmarks from *other*
+    /// overwrite conflicting slots and sprite ids already used in this scene get cleared.
+    ///
+    /// # Returns
+    ///
+    /// * `SceneV3` - merged scene
+    ///
+    pub fn merge(mut self, other: SceneV3) -> SceneV3 {
+        let ids: BTreeSet<u32> = self
+            .objects
+            .iter()
+            .filter_map(|(sprite, _)| sprite.id)
+            .collect();
+
+        for (mut sprite, pos) in other.objects {
+            if sprite.id.map_or(false, |id| ids.contains(&id)) {
+                sprite.id = None;
+            }
+            self.objects.push((sprite, pos));
+        }
+        self.bookmarks.extend(other.bookmarks);
+
+        self
+    }
+
+    /// Compares scene contents ignoring order of objects, useful for save/load checks
+    pub fn content_eq(&self, other: &SceneV3) -> bool {
+        if self.objects.len() != other.objects.len() || self.bookmarks != other.bookmarks {
+            return false;
+        }
+
+        let mut unmatched: Vec<&(Sprite, Position)> = other.objects.iter().collect();
+        for obj in &self.objects {
+            match unmatched.iter().position(|o| *o == obj) {
+                Some(i) => {
+                    unmatched.swap_remove(i);
+                }
+                None => return false,
+            }
+        }
+
+        true
+    }
+
+    /// Sets bookmark *slot* to given position
+    pub fn set_bookmark(&mut self, slot: usize, pos: Position2D) {
+        self.bookmarks.insert(slot, pos);
+    }
+
+    /// Position of bookmark in given *slot* if set
+    pub fn bookmark(&self, slot: usize) -> Option<Position2D> {
+        self.bookmarks.get(&slot).copied()
+    }
+
+    /// Clears bookmark in given *slot*, false if it wasn't set
+    pub fn clear_bookmark(&mut self, slot: usize) -> bool {
+        self.bookmarks.remove(&slot).is_some()
+    }
+
+    /// Next bookmark after given *slot*, wraps around to the first one
+    pub fn next_bookmark(&self, after: usize) -> Option<(usize, Position2D)> {
+        self.bookmarks
+            .range((Bound::Excluded(after), Bound::Unbounded))
+            .next()
+            .or_else(|| self.bookmarks.iter().next())
+            .map(|(slot, pos)| (*slot, *pos))
+    }
+
+    /// Moves all objects whose sprite has given *label* by *offset*
+    pub fn translate_group(&mut self, label: &str, offset: Position2D) {
+        for (_, pos) in self
+            .objects
+            .iter_mut()
+            .filter(|(sprite, _)| sprite.labels.contains_key(label))
+        {
+            *pos += offset;
+        }
+    }
+
+    /// Removes all objects whose sprite has given *label*, returns count removed
+    pub fn remove_group(&mut self, label: &str) -> usize {
+        let count = self.objects.len();
+        self.objects
+            .retain(|(sprite, _)| !sprite.labels.contains_key(label));
+
+        count - self.objects.len()
+    }
+}
+
+// area covered by sprite's texels placed at given position, texels
+// don't need to start at sprite origin
+fn world_bounds(sprite: &Sprite, pos: &Position) -> Bounds {
+    let bounds = sprite.content_bounds();
+
+    Bounds::Free(
+        *bounds.position() + Position2D::from(pos),
+        *bounds.dimension(),
+    )
+}
+
 ///
 /// Scene is the final serialization artifact for texel_types.
 /// As such it needs to be versioned explicitly so it can be known which version
@@ -66,11 +405,12 @@ impl From<SceneV1> for SceneV2 {
 pub enum Scene {
     V1(SceneV1),
     V2(SceneV2),
+    V3(SceneV3),
 }
 
 impl Default for Scene {
     fn default() -> Self {
-        Scene::V2(SceneV2::default())
+        Scene::V3(SceneV3::default())
     }
 }
 
@@ -82,12 +422,664 @@ impl Scene {
     ///
     /// # Returns
     ///
-    /// * `SceneV2` - current scene version
+    /// * `SceneV3` - current scene version
     ///
-    pub fn current(self) -> SceneV2 {
+    pub fn current(self) -> SceneV3 {
         match self {
-            Self::V2(scene) => scene,
-            Self::V1(scene) => SceneV2::from(scene),
+            Self::V3(scene) => scene,
+            Self::V2(scene) => SceneV3::from(scene),
+            Self::V1(scene) => SceneV3::from(SceneV2::from(scene)),
+        }
+    }
+
+    ///
+    /// Detects version of a serialized `Scene` from its enum tag without decoding
+    /// the contents. Assumes the default `bincode` encoding where the variant index
+    /// is stored as a little endian u32 at the start.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u32>` - scene version number, None if unknown or too short
+    ///
+    #[cfg(feature = "serde_support")]
+    pub fn peek_version(bytes: &[u8]) -> Option<u32> {
+        let tag: [u8; 4] = bytes.get(..4)?.try_into().ok()?;
+
+        match u32::from_le_bytes(tag) {
+            0 => Some(1),
+            1 => Some(2),
+            2 => Some(3),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{texels_from_str, Texels};
+    use std::collections::HashMap;
+
+    fn at(x: i32, y: i32, z: i32) -> Position {
+        Position { x, y, z }
+    }
+
+    fn scene_v2() -> SceneV2 {
+        let mut scene = SceneV2::default();
+        let sprite = SpriteV2 {
+            frames: vec![Texels::new(), Texels::new()],
+            index: 1,
+            id: Some(3),
+            labels: HashMap::new(),
+        };
+        scene.objects.push((sprite, Position::default()));
+        scene.bookmarks.insert(0, Position2D::from_xy(2, 1));
+        scene
+    }
+
+    #[test]
+    fn current_upgrades_v2_with_empty_frame_names() {
+        let scene = Scene::V2(scene_v2()).current();
+        let sprite = &scene.objects[0].0;
+
+        assert_eq!(sprite.frame_names, vec![None, None]);
+        assert_eq!(sprite.index, 1);
+        assert_eq!(sprite.id, Some(3));
+        assert_eq!(scene.bookmarks.get(&0), Some(&Position2D::from_xy(2, 1)));
+    }
+
+    #[cfg(feature = "serde_support")]
+    #[test]
+    fn bincode_v2_scene_loads_as_v3() {
+        let bytes = bincode::serialize(&Scene::V2(scene_v2())).unwrap();
+        let scene: Scene = bincode::deserialize(&bytes).unwrap();
+
+        assert_eq!(scene.current().objects[0].0.frame_names, vec![None, None]);
+    }
+
+    #[cfg(feature = "serde_support")]
+    #[test]
+    fn bincode_v3_scene_keeps_frame_names() {
+        let mut scene = Scene::V2(scene_v2()).current();
+        scene.objects[0]
+            .0
+            .set_frame_name(1, Some("walk".to_owned()));
+
+        let bytes = bincode::serialize(&Scene::V3(scene)).unwrap();
+        let loaded: Scene = bincode::deserialize(&bytes).unwrap();
+
+        assert_eq!(loaded.current().objects[0].0.frame_name(1), Some("walk"));
+    }
+
+    fn labeled(label: &str) -> Sprite {
+        let mut sprite = Sprite::from_texels(texels_from_str("x", Position2D::default()));
+        sprite.labels.insert(label.to_owned(), String::new());
+
+        sprite
+    }
+
+    #[test]
+    fn translate_group_moves_labeled_sprites() {
+        let mut scene = SceneV3::default();
+        scene.objects.push((labeled("wall"), at(0, 0, 0)));
+        scene.objects.push((labeled("wall"), at(2, 3, 1)));
+        scene.objects.push((labeled("player"), at(5, 5, 0)));
+
+        scene.translate_group("wall", Position2D::from_xy(1, -1));
+
+        assert_eq!(scene.objects[0].1, at(1, -1, 0));
+        assert_eq!(scene.objects[1].1, at(3, 2, 1));
+        assert_eq!(scene.objects[2].1, at(5, 5, 0));
+    }
+
+    #[test]
+    fn remove_group_removes_labeled_sprites() {
+        let mut scene = SceneV3::default();
+        scene.objects.push((labeled("wall"), at(0, 0, 0)));
+        scene.objects.push((labeled("player"), at(5, 5, 0)));
+        scene.objects.push((labeled("wall"), at(2, 3, 1)));
+
+        assert_eq!(scene.remove_group("wall"), 2);
+        assert_eq!(scene.remove_group("wall"), 0);
+        assert_eq!(scene.objects.len(), 1);
+        assert!(scene.objects[0].0.labels.contains_key("player"));
+    }
+
+    fn with_id(id: u32, symbol: &str) -> Sprite {
+        let mut sprite = Sprite::from_texels(texels_from_str(symbol, Position2D::default()));
+        sprite.id = Some(id);
+
+        sprite
+    }
+
+    #[test]
+    fn sprite_by_id_present_and_absent() {
+        let mut scene = SceneV3::default();
+        scene.objects.push((with_id(1, "a"), at(0, 0, 0)));
+        scene.objects.push((with_id(2, "b"), at(5, 5, 0)));
+
+        assert_eq!(
+            scene.sprite_by_id(2).map(|(_, pos)| *pos),
+            Some(at(5, 5, 0))
+        );
+        assert!(scene.sprite_by_id(3).is_none());
+        assert!(scene.sprite_by_id_mut(3).is_none());
+    }
+
+    #[test]
+    fn sprite_by_id_duplicates_return_first() {
+        let mut scene = SceneV3::default();
+        scene.objects.push((with_id(1, "a"), at(0, 0, 0)));
+        scene.objects.push((with_id(1, "b"), at(1, 0, 0)));
+
+        assert_eq!(
+            scene.sprite_by_id(1).map(|(_, pos)| *pos),
+            Some(at(0, 0, 0))
+        );
+        if let Some((_, pos)) = scene.sprite_by_id_mut(1) {
+            pos.x = 9;
         }
+        assert_eq!(scene.objects[0].1, at(9, 0, 0));
+        assert_eq!(scene.objects[1].1, at(1, 0, 0));
+    }
+
+    #[test]
+    fn sprites_with_label_multiple_matches() {
+        let mut scene = SceneV3::default();
+        scene.objects.push((labeled("background"), at(0, 0, 0)));
+        scene.objects.push((labeled("hero"), at(1, 0, 0)));
+        scene.objects.push((labeled("background"), at(2, 0, 0)));
+
+        let found: Vec<Position> = scene
+            .sprites_with_label("background")
+            .map(|(_, pos)| *pos)
+            .collect();
+        assert_eq!(found, vec![at(0, 0, 0), at(2, 0, 0)]);
+    }
+
+    #[test]
+    fn sprites_with_label_unknown() {
+        let mut scene = SceneV3::default();
+        scene.objects.push((labeled("hero"), at(0, 0, 0)));
+
+        assert_eq!(scene.sprites_with_label("enemy").count(), 0);
+        assert_eq!(scene.sprites_with_label("").count(), 0);
+    }
+
+    #[test]
+    fn add_sprite_sequential_ids() {
+        let mut scene = SceneV3::default();
+
+        let first = scene.add_sprite(labeled("a"), at(0, 0, 0));
+        let second = scene.add_sprite(labeled("b"), at(1, 0, 0));
+        let third = scene.add_sprite(labeled("c"), at(2, 0, 0));
+        assert!(first < second && second < third);
+        assert_eq!(scene.objects[1].0.id, Some(second));
+    }
+
+    #[test]
+    fn add_sprite_keeps_unused_own_id() {
+        let mut scene = SceneV3::default();
+        let mut own = labeled("a");
+        own.id = Some(10);
+
+        assert_eq!(scene.add_sprite(own.clone(), at(0, 0, 0)), 10);
+        assert_eq!(scene.add_sprite(own, at(0, 0, 0)), 11);
+    }
+
+    #[test]
+    fn remove_sprite_returns_object() {
+        let mut scene = SceneV3::default();
+        scene.add_sprite(labeled("a"), at(0, 0, 0));
+        let id = scene.add_sprite(labeled("b"), at(3, 4, 5));
+
+        let (sprite, pos) = scene.remove_sprite(id).unwrap();
+        assert!(sprite.has_label("b"));
+        assert_eq!(pos, at(3, 4, 5));
+        assert_eq!(scene.objects.len(), 1);
+        assert!(scene.remove_sprite(id).is_none());
+    }
+
+    #[test]
+    fn flatten_higher_z_wins() {
+        let mut scene = SceneV3::default();
+        let top = Sprite::from_texels(texels_from_str("xy", Position2D::default()));
+        let bottom = Sprite::from_texels(texels_from_str("abc", Position2D::default()));
+        scene.objects.push((top, at(1, 1, 2)));
+        scene.objects.push((bottom, at(0, 1, 1)));
+
+        let flat = scene.flatten();
+        let cells: Vec<(char, i32, i32)> =
+            flat.iter().map(|t| (t.symbol, t.pos.x, t.pos.y)).collect();
+        assert_eq!(cells, vec![('a', 0, 1), ('x', 1, 1), ('y', 2, 1)]);
+    }
+
+    #[test]
+    fn flatten_empty_scene() {
+        assert!(SceneV3::default().flatten().is_empty());
+    }
+
+    #[test]
+    fn bookmarks_set_get_clear() {
+        let mut scene = SceneV3::default();
+        let pos = Position2D::from_xy(3, 4);
+
+        scene.set_bookmark(1, pos);
+        assert_eq!(scene.bookmark(1), Some(pos));
+        assert_eq!(scene.bookmark(2), None);
+        assert!(scene.clear_bookmark(1));
+        assert!(!scene.clear_bookmark(1));
+        assert_eq!(scene.bookmark(1), None);
+    }
+
+    #[test]
+    fn next_bookmark_cycles() {
+        let mut scene = SceneV3::default();
+        assert_eq!(scene.next_bookmark(0), None);
+
+        scene.set_bookmark(2, Position2D::from_xy(2, 0));
+        scene.set_bookmark(5, Position2D::from_xy(5, 0));
+        assert_eq!(scene.next_bookmark(0), Some((2, Position2D::from_xy(2, 0))));
+        assert_eq!(scene.next_bookmark(2), Some((5, Position2D::from_xy(5, 0))));
+        assert_eq!(scene.next_bookmark(5), Some((2, Position2D::from_xy(2, 0))));
+    }
+
+    #[test]
+    fn content_eq_ignores_object_order() {
+        let mut a = SceneV3::default();
+        a.add_sprite(labeled("a"), at(0, 0, 0));
+        a.add_sprite(labeled("b"), at(1, 2, 3));
+        a.set_bookmark(0, Position2D::from_xy(1, 1));
+
+        let mut b = a.clone();
+        b.objects.reverse();
+        assert!(a.content_eq(&b));
+        assert!(b.content_eq(&a));
+    }
+
+    #[test]
+    fn content_eq_detects_differences() {
+        let mut a = SceneV3::default();
+        a.add_sprite(labeled("a"), at(0, 0, 0));
+
+        let mut moved = a.clone();
+        moved.objects[0].1 = at(1, 0, 0);
+        assert!(!a.content_eq(&moved));
+
+        let mut bookmarked = a.clone();
+        bookmarked.set_bookmark(0, Position2D::default());
+        assert!(!a.content_eq(&bookmarked));
+
+        let mut extra = a.clone();
+        extra.add_sprite(labeled("b"), at(0, 0, 0));
+        assert!(!a.content_eq(&extra));
+    }
+
+    fn scene_v1() -> SceneV1 {
+        let frames = vec![texels_from_str("ab", Position2D::default()); 2];
+
+        SceneV1 {
+            objects: vec![
+                (SpriteV1 { frames, index: 1 }, at(1, 2, 3), true),
+                (
+                    SpriteV1 {
+                        frames: vec![Texels::new()],
+                        index: 0,
+                    },
+                    at(0, 0, 0),
+                    false,
+                ),
+            ],
+        }
+    }
+
+    #[test]
+    fn current_upgrades_v1_to_v3() {
+        let scene = Scene::V1(scene_v1()).current();
+
+        assert_eq!(scene.objects.len(), 2);
+        let (sprite, pos) = &scene.objects[0];
+        assert_eq!(*pos, at(1, 2, 3));
+        assert_eq!(sprite.frame_count(), 2);
+        assert_eq!(sprite.frame_index(), 1);
+        assert_eq!(sprite.frame_names, vec![None, None]);
+        assert_eq!(sprite.durations, vec![0, 0]);
+        assert_eq!(sprite.id, None);
+        assert!(scene.bookmarks.is_empty());
+    }
+
+    #[test]
+    fn current_upgrades_v2_keeping_ids_and_bookmarks() {
+        let mut v2 = SceneV2::from(scene_v1());
+        v2.objects[0].0.id = Some(4);
+        v2.bookmarks.insert(1, Position2D::from_xy(5, 5));
+
+        let scene = Scene::V2(v2).current();
+        assert_eq!(
+            scene.sprite_by_id(4).map(|(_, pos)| *pos),
+            Some(at(1, 2, 3))
+        );
+        assert_eq!(scene.bookmark(1), Some(Position2D::from_xy(5, 5)));
+        assert_eq!(scene.objects[1].0.durations, vec![0]);
+    }
+
+    #[cfg(feature = "serde_support")]
+    #[test]
+    fn deserialized_v1_scene_upgrades_to_v3() {
+        let bytes = bincode::serialize(&Scene::V1(scene_v1())).unwrap();
+        let scene: Scene = bincode::deserialize(&bytes).unwrap();
+
+        let expected = Scene::V1(scene_v1()).current();
+        let current = scene.current();
+        assert!(current.content_eq(&expected));
+        assert_eq!(current.objects[0].0.frames[1].len(), 2);
+    }
+
+    #[cfg(feature = "serde_support")]
+    #[test]
+    fn v3_scene_round_trip() {
+        let mut scene = Scene::V1(scene_v1()).current();
+        scene.objects[0].0.set_frame_duration(1, 100);
+
+        let bytes = bincode::serialize(&Scene::V3(scene.clone())).unwrap();
+        let back: Scene = bincode::deserialize(&bytes).unwrap();
+        assert!(back.current().content_eq(&scene));
+    }
+
+    #[cfg(feature = "serde_support")]
+    #[test]
+    fn peek_version_of_each_variant() {
+        let scenes = [
+            (Scene::V1(SceneV1::default()), 1),
+            (Scene::V2(SceneV2::default()), 2),
+            (Scene::V3(SceneV3::default()), 3),
+        ];
+
+        for (scene, version) in scenes.iter() {
+            let bytes = bincode::serialize(scene).unwrap();
+            assert_eq!(Scene::peek_version(&bytes), Some(*version));
+        }
+    }
+
+    #[cfg(feature = "serde_support")]
+    #[test]
+    fn peek_version_unknown_or_short() {
+        assert_eq!(Scene::peek_version(&[]), None);
+        assert_eq!(Scene::peek_version(&[1, 0]), None);
+        assert_eq!(Scene::peek_version(&[9, 0, 0, 0]), None);
+    }
+
+    #[cfg(feature = "serde_support")]
+    #[test]
+    fn peek_version_of_populated_scene() {
+        let scene = Scene::V3(Scene::V1(scene_v1()).current());
+        let bytes = bincode::serialize(&scene).unwrap();
+
+        assert_eq!(Scene::peek_version(&bytes), Some(3));
+        assert_eq!(Scene::peek_version(&bytes[..4]), Some(3));
+    }
+
+    #[test]
+    fn remove_sprite_with_duplicate_ids() {
+        let mut scene = SceneV3::default();
+        scene.objects.push((with_id(1, "a"), at(0, 0, 0)));
+        scene.objects.push((with_id(2, "b"), at(1, 0, 0)));
+        scene.objects.push((with_id(1, "c"), at(2, 0, 0)));
+
+        assert!(scene.remove_sprite(5).is_none());
+        assert_eq!(
+            scene.remove_sprite(1).map(|(_, pos)| pos),
+            Some(at(0, 0, 0))
+        );
+        assert_eq!(scene.objects.len(), 2);
+        assert_eq!(scene.objects[0].1, at(1, 0, 0));
+        assert_eq!(
+            scene.sprite_by_id(1).map(|(_, pos)| *pos),
+            Some(at(2, 0, 0))
+        );
+    }
+
+    #[test]
+    fn find_and_remove_by_id() {
+        let mut scene = SceneV3::default();
+        scene.objects.push((with_id(1, "a"), at(0, 0, 0)));
+        scene.objects.push((with_id(1, "b"), at(1, 0, 0)));
+
+        assert_eq!(scene.find_by_id(1).map(|(_, pos)| *pos), Some(at(0, 0, 0)));
+        assert!(scene.find_by_id(2).is_none());
+        if let Some((_, pos)) = scene.find_by_id_mut(1) {
+            pos.z = 3;
+        }
+        assert_eq!(scene.remove_by_id(1).map(|(_, pos)| pos), Some(at(0, 0, 3)));
+        assert!(scene.remove_by_id(2).is_none());
+        assert_eq!(scene.objects.len(), 1);
+    }
+
+    #[test]
+    fn sprites_with_label_exact_match_only() {
+        let mut scene = SceneV3::default();
+        scene.objects.push((labeled("wall"), at(0, 0, 0)));
+        scene.objects.push((labeled("wallpaper"), at(1, 0, 0)));
+        scene.objects.push((labeled(""), at(2, 0, 0)));
+
+        assert_eq!(scene.sprites_with_label("wall").count(), 1);
+        assert_eq!(scene.sprites_with_label("all").count(), 0);
+        assert_eq!(
+            scene
+                .sprites_with_label("")
+                .map(|(_, pos)| *pos)
+                .collect::<Vec<_>>(),
+            vec![at(2, 0, 0)]
+        );
+    }
+
+    #[test]
+    fn sprites_with_label_mut_updates_matches() {
+        let mut scene = SceneV3::default();
+        scene.objects.push((labeled("wall"), at(0, 0, 0)));
+        scene.objects.push((labeled("player"), at(0, 0, 0)));
+        scene.objects.push((labeled("wall"), at(0, 0, 0)));
+
+        for (_, pos) in scene.sprites_with_label_mut("wall") {
+            pos.z = 3;
+        }
+        let z: Vec<i32> = scene.objects.iter().map(|(_, pos)| pos.z).collect();
+        assert_eq!(z, vec![3, 0, 3]);
+        assert_eq!(scene.sprites_with_label_mut("enemy").count(), 0);
+    }
+
+    fn sprite_at_origin(s: &str) -> Sprite {
+        Sprite::from_texels(texels_from_str(s, Position2D::default()))
+    }
+
+    #[test]
+    fn bounding_box_of_sprite_away_from_origin() {
+        let mut scene = SceneV3::default();
+        let sprite = Sprite::from_texels(texels_from_str("x", Position2D::from_xy(5, 0)));
+        scene.add_sprite(sprite, at(1, 1, 0));
+
+        assert_eq!(
+            scene.bounding_box(),
+            Bounds::Free(Position2D::from_xy(6, 1), Dimension::unit())
+        );
+    }
+
+    #[test]
+    fn sprites_overlapping_sprite_away_from_origin() {
+        let mut scene = SceneV3::default();
+        let sprite = Sprite::from_texels(texels_from_str("x", Position2D::from_xy(5, 0)));
+        scene.add_sprite(sprite, at(1, 1, 0));
+
+        let origin = Bounds::Free(Position2D::from_xy(1, 1), Dimension::unit());
+        let texel = Bounds::Free(Position2D::from_xy(6, 1), Dimension::unit());
+        assert_eq!(scene.sprites_overlapping(origin).count(), 0);
+        assert_eq!(scene.sprites_overlapping(texel).count(), 1);
+    }
+
+    #[test]
+    fn bounding_box_empty_scene() {
+        let mut scene = SceneV3::default();
+        assert_eq!(scene.bounding_box(), Bounds::empty());
+
+        scene.add_sprite(Sprite::default(), at(4, 4, 0));
+        assert_eq!(scene.bounding_box(), Bounds::empty());
+    }
+
+    #[test]
+    fn bounding_box_single_sprite() {
+        let mut scene = SceneV3::default();
+        scene.add_sprite(sprite_at_origin("ab\ncd"), at(2, 3, 0));
+
+        assert_eq!(
+            scene.bounding_box(),
+            Bounds::Free(Position2D::from_xy(2, 3), Dimension::from_wh(2, 2))
+        );
+    }
+
+    #[test]
+    fn bounding_box_separate_sprites() {
+        let mut scene = SceneV3::default();
+        scene.add_sprite(sprite_at_origin("ab"), at(0, 0, 0));
+        scene.add_sprite(sprite_at_origin("c"), at(4, 2, 1));
+
+        assert_eq!(
+            scene.bounding_box(),
+            Bounds::Free(Position2D::from_xy(0, 0), Dimension::from_wh(5, 3))
+        );
+    }
+
+    #[test]
+    fn bounding_box_overlapping_sprites() {
+        let mut scene = SceneV3::default();
+        scene.add_sprite(sprite_at_origin("abc\ndef"), at(0, 0, 0));
+        scene.add_sprite(sprite_at_origin("xy\nzw"), at(-1, 1, 0));
+
+        assert_eq!(
+            scene.bounding_box(),
+            Bounds::Free(Position2D::from_xy(-1, 0), Dimension::from_wh(4, 3))
+        );
+    }
+
+    fn spread_scene() -> SceneV3 {
+        let mut scene = SceneV3::default();
+        scene.add_sprite(sprite_at_origin("ab"), at(0, 0, 0));
+        scene.add_sprite(sprite_at_origin("ab"), at(3, 3, 0));
+        scene.add_sprite(sprite_at_origin("ab"), at(10, 10, 0));
+
+        scene
+    }
+
+    #[test]
+    fn sprites_in_includes_boundary() {
+        let scene = spread_scene();
+        let area = Bounds::Free(Position2D::default(), Dimension::from_wh(4, 4));
+
+        let found: Vec<Position> = scene.sprites_in(area).map(|(_, pos)| *pos).collect();
+        assert_eq!(found, vec![at(0, 0, 0), at(3, 3, 0)]);
+    }
+
+    #[test]
+    fn sprites_in_and_overlapping_outside() {
+        let scene = spread_scene();
+        let area = Bounds::Free(Position2D::from_xy(20, 0), Dimension::from_wh(4, 4));
+
+        assert_eq!(scene.sprites_in(area).count(), 0);
+        assert_eq!(scene.sprites_overlapping(area).count(), 0);
+    }
+
+    #[test]
+    fn sprites_overlapping_partial() {
+        let scene = spread_scene();
+        let area = Bounds::Free(Position2D::from_xy(4, 0), Dimension::from_wh(7, 4));
+
+        let found: Vec<Position> = scene
+            .sprites_overlapping(area)
+            .map(|(_, pos)| *pos)
+            .collect();
+        assert_eq!(found, vec![at(3, 3, 0)]);
+        assert_eq!(scene.sprites_in(area).count(), 0);
+    }
+
+    #[test]
+    fn z_order_sorted_objects_ascending_stable() {
+        let mut scene = SceneV3::default();
+        scene.objects.push((labeled("a"), at(0, 0, 2)));
+        scene.objects.push((labeled("b"), at(0, 0, -1)));
+        scene.objects.push((labeled("c"), at(0, 0, 2)));
+        scene.objects.push((labeled("d"), at(0, 0, 0)));
+
+        let order: Vec<bool> = ["b", "d", "a", "c"]
+            .iter()
+            .zip(scene.z_order_sorted_objects())
+            .map(|(label, (sprite, _))| sprite.has_label(label))
+            .collect();
+        assert_eq!(order, vec![true; 4]);
+        assert!(scene.objects[0].0.has_label("a"));
+    }
+
+    #[test]
+    fn merge_appends_objects_and_bookmarks() {
+        let mut a = SceneV3::default();
+        a.add_sprite(labeled("a"), at(0, 0, 0));
+        a.set_bookmark(0, Position2D::from_xy(0, 0));
+        a.set_bookmark(1, Position2D::from_xy(1, 1));
+
+        let mut b = SceneV3::default();
+        b.add_sprite(labeled("b"), at(1, 0, 0));
+        b.add_sprite(labeled("c"), at(2, 0, 0));
+        b.set_bookmark(1, Position2D::from_xy(9, 9));
+
+        let merged = a.merge(b);
+        assert_eq!(merged.objects.len(), 3);
+        assert_eq!(merged.bookmark(0), Some(Position2D::from_xy(0, 0)));
+        assert_eq!(merged.bookmark(1), Some(Position2D::from_xy(9, 9)));
+    }
+
+    #[test]
+    fn merge_clears_conflicting_ids() {
+        let mut a = SceneV3::default();
+        a.objects.push((with_id(1, "a"), at(0, 0, 0)));
+
+        let mut b = SceneV3::default();
+        b.objects.push((with_id(1, "b"), at(1, 0, 0)));
+        b.objects.push((with_id(2, "c"), at(2, 0, 0)));
+        b.objects.push((Sprite::default(), at(3, 0, 0)));
+
+        let merged = a.merge(b);
+        let ids: Vec<Option<u32>> = merged.objects.iter().map(|(sprite, _)| sprite.id).collect();
+        assert_eq!(ids, vec![Some(1), None, Some(2), None]);
+    }
+
+    #[test]
+    fn render_to_grid_topmost_texel() {
+        let mut scene = SceneV3::default();
+        scene.add_sprite(sprite_at_origin("abc"), at(0, 0, 0));
+        scene.add_sprite(sprite_at_origin("x y"), at(0, 0, 1));
+        let viewport = Bounds::Free(Position2D::default(), Dimension::from_wh(4, 2));
+
+        let grid = scene.render_to_grid(viewport);
+        assert_eq!(grid.len(), 2);
+        assert!(grid.iter().all(|row| row.len() == 4));
+        let top: Vec<Option<char>> = grid[0]
+            .iter()
+            .map(|c| c.as_ref().map(|t| t.symbol))
+            .collect();
+        assert_eq!(top, vec![Some('x'), Some('b'), Some('y'), None]);
+        assert!(grid[1].iter().all(|c| c.is_none()));
+    }
+
+    #[test]
+    fn render_to_grid_clips_to_viewport() {
+        let mut scene = SceneV3::default();
+        scene.add_sprite(sprite_at_origin("abcd"), at(-2, 1, 0));
+        scene.add_sprite(sprite_at_origin("z"), at(10, 10, 0));
+        let viewport = Bounds::Free(Position2D::from_xy(-1, 1), Dimension::from_wh(2, 1));
+
+        let grid = scene.render_to_grid(viewport);
+        let row: Vec<Option<(char, i32)>> = grid[0]
+            .iter()
+            .map(|c| c.as_ref().map(|t| (t.symbol, t.pos.x)))
+            .collect();
+        assert_eq!(row, vec![Some(('b', -1)), Some(('c', 0))]);
     }
 }
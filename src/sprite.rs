@@ -1,8 +1,11 @@
 use crate::{
-    Bounds, ColorMode, Dimension, Position2D, SymbolStyle, SymbolStyles, Texel, Texels, Which,
+    texels_in_bounds, Bounds, ColorMode, Dimension, Direction, Position2D, SymbolStyle,
+    SymbolStyles, Texel, Texels, Which,
 };
-use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 use std::fs::File;
+use std::hash::{Hash, Hasher};
 use std::io::Read;
 use std::path::Path;
 
@@ -20,7 +23,12 @@ pub const SPRITE_MAX_BYTES: usize = u16::max_value() as usize;
 ///
 /// Sprite represents a 2D ASCII art picture with frame animation
 ///
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// #### Compatibility
+/// Per frame metadata (`frame_names` and `durations`) changes the serialized
+/// shape and was introduced with `SceneV3`. Sprites stored in older scenes are read as
+/// `SpriteV2` or `SpriteV1` and converted with empty metadata for each frame.
+///
+#[derive(Debug, Clone, Eq)]
 #[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
 pub struct Sprite {
     /// List of Frame data consisting of texels
@@ -31,6 +39,12 @@ pub struct Sprite {
     pub id: Option<u32>,
     /// Optional list of labels for grouping sprites in a scene, key value pair labels
     pub labels: HashMap<String, String>,
+    /// Optional frame names for animation states, kept in sync with frames
+    #[cfg_attr(feature = "serde_support", serde(default))]
+    pub frame_names: Vec<Option<String>>,
+    /// Optional frame durations in milliseconds for animation playback, 0 for unspecified
+    #[cfg_attr(feature = "serde_support", serde(default))]
+    pub durations: Vec<u16>,
 }
 
 ///
@@ -43,9 +57,21 @@ pub struct SpriteV1 {
     pub index: usize,
 }
 
-impl From<SpriteV1> for Sprite {
+///
+/// Previous version of the sprite without frame metadata for re-import in scene only
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub struct SpriteV2 {
+    pub frames: Vec<Texels>,
+    pub index: usize,
+    pub id: Option<u32>,
+    pub labels: HashMap<String, String>,
+}
+
+impl From<SpriteV1> for SpriteV2 {
     fn from(old: SpriteV1) -> Self {
-        Sprite {
+        SpriteV2 {
             frames: old.frames,
             index: old.index,
             id: None,
@@ -54,17 +80,56 @@ impl From<SpriteV1> for Sprite {
     }
 }
 
-impl Default for Sprite {
-    fn default() -> Self {
+impl From<SpriteV2> for Sprite {
+    fn from(old: SpriteV2) -> Self {
         Sprite {
-            frames: vec![Texels::new()],
-            index: 0,
-            id: None,
-            labels: HashMap::new(),
+            index: old.index,
+            id: old.id,
+            labels: old.labels,
+            ..Sprite::with_frames(old.frames)
+        }
+    }
+}
+
+impl From<SpriteV1> for Sprite {
+    fn from(old: SpriteV1) -> Self {
+        Sprite::from(SpriteV2::from(old))
+    }
+}
+
+/// Lossy downgrade dropping frame names and durations
+impl From<Sprite> for SpriteV2 {
+    fn from(sprite: Sprite) -> Self {
+        SpriteV2 {
+            frames: sprite.frames,
+            index: sprite.index,
+            id: sprite.id,
+            labels: sprite.labels,
         }
     }
 }
 
+// frame metadata is compared per frame, missing entries count as empty
+// since frames are public and can change without the metadata following
+impl PartialEq for Sprite {
+    fn eq(&self, other: &Self) -> bool {
+        self.frames == other.frames
+            && self.index == other.index
+            && self.id == other.id
+            && self.labels == other.labels
+            && (0..self.frames.len()).all(|i| {
+                self.frame_name(i) == other.frame_name(i)
+                    && self.frame_duration(i) == other.frame_duration(i)
+            })
+    }
+}
+
+impl Default for Sprite {
+    fn default() -> Self {
+        Sprite::with_frames(vec![Texels::new()])
+    }
+}
+
 impl IntoIterator for Sprite {
     type Item = Texel;
     type IntoIter = ::std::vec::IntoIter<Self::Item>;
@@ -92,15 +157,108 @@ impl Sprite {
 
     /// Creates a new frame copying contents of current frame
     pub fn new_frame(&mut self) {
+        self.sync_frame_meta();
         self.frames
             .insert(self.index, self.frames[self.index].clone());
+        self.frame_names.insert(self.index + 1, None);
+        self.durations.insert(self.index + 1, 0);
         self.apply_frame_change(Which::Next);
     }
 
+    /// Creates a new empty frame after current frame and selects it
+    pub fn new_frame_blank(&mut self) {
+        self.insert_blank_frame(self.index + 1);
+    }
+
+    /// Inserts a new empty frame at given index (clamped to frame count) and selects it
+    pub fn insert_blank_frame(&mut self, at: usize) {
+        let at = std::cmp::min(at, self.frames.len());
+
+        if self.insert_frame_at(at, Texels::new()).is_ok() {
+            self.index = at;
+        }
+    }
+
+    /// Inserts frame with given *texels* at *index* keeping current frame selected,
+    /// empty Error if index is past frame count
+    #[allow(clippy::result_unit_err)]
+    pub fn insert_frame_at(&mut self, index: usize, texels: Texels) -> Result<(), ()> {
+        if index > self.frames.len() {
+            return Err(());
+        }
+
+        self.sync_frame_meta();
+        self.frames.insert(index, texels);
+        self.frame_names.insert(index, None);
+        self.durations.insert(index, 0);
+
+        if index <= self.index && self.frames.len() > 1 {
+            self.index += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Pads frames with empty ones so that frame at given index exists
+    pub fn ensure_frame(&mut self, index: usize) {
+        if index >= self.frames.len() {
+            self.frames.resize(index + 1, Texels::new());
+            self.sync_frame_meta();
+        }
+    }
+
+    /// Moves frame at *from* index to *to* index selecting it, false if out of bounds
+    pub fn move_frame(&mut self, from: usize, to: usize) -> bool {
+        if from >= self.frames.len() || to >= self.frames.len() {
+            return false;
+        }
+
+        self.sync_frame_meta();
+        let frame = self.frames.remove(from);
+        self.frames.insert(to, frame);
+        let name = self.frame_names.remove(from);
+        self.frame_names.insert(to, name);
+        let duration = self.durations.remove(from);
+        self.durations.insert(to, duration);
+        self.index = to;
+
+        true
+    }
+
+    /// Swaps frames at given indexes keeping current index, false if out of bounds
+    pub fn swap_frames(&mut self, a: usize, b: usize) -> bool {
+        if a >= self.frames.len() || b >= self.frames.len() {
+            return false;
+        }
+
+        self.sync_frame_meta();
+        self.frames.swap(a, b);
+        self.frame_names.swap(a, b);
+        self.durations.swap(a, b);
+
+        true
+    }
+
+    /// Reverses order of frames keeping the current frame selected
+    pub fn reverse_frames(&mut self) {
+        if self.frames.is_empty() {
+            return;
+        }
+
+        self.sync_frame_meta();
+        self.frames.reverse();
+        self.frame_names.reverse();
+        self.durations.reverse();
+        self.index = self.frames.len() - 1 - self.index;
+    }
+
     /// Deletes current frame
     pub fn delete_frame(&mut self) -> bool {
         if self.frames.len() > 1 {
+            self.sync_frame_meta();
             self.frames.remove(self.index);
+            self.frame_names.remove(self.index);
+            self.durations.remove(self.index);
             self.apply_frame_change(Which::Previous);
             true
         } else {
@@ -108,6 +266,41 @@ impl Sprite {
         }
     }
 
+    /// Removes frames with the same content as their preceding frame, current frame
+    /// moves to its remaining duplicate if removed. Returns count of removed frames
+    pub fn deduplicate_frames(&mut self) -> usize {
+        let mut removed = 0;
+        let mut i = 1;
+
+        self.sync_frame_meta();
+        while i < self.frames.len() {
+            // hashes only rule out duplicates cheaply, collisions are possible
+            if self.frame_hash(i) == self.frame_hash(i - 1)
+                && same_content(&self.frames[i], &self.frames[i - 1])
+            {
+                self.frames.remove(i);
+                self.frame_names.remove(i);
+                self.durations.remove(i);
+                if self.index >= i {
+                    self.index -= 1;
+                }
+                removed += 1;
+            } else {
+                i += 1;
+            }
+        }
+
+        removed
+    }
+
+    /// Checks if sprite has at least two frames with different contents
+    pub fn is_animated(&self) -> bool {
+        match self.frames.split_first() {
+            Some((first, rest)) => rest.iter().any(|frame| !same_content(first, frame)),
+            None => false,
+        }
+    }
+
     /// Applies given frame change according to the `which` argument
     pub fn apply_frame_change(&mut self, which: Which<usize>) -> usize {
         match which {
@@ -122,6 +315,65 @@ impl Sprite {
         }
     }
 
+    /// Applies given frame change according to the `which` argument,
+    /// true if current frame index changed
+    pub fn try_frame_change(&mut self, which: Which<usize>) -> bool {
+        let old_index = self.index;
+
+        self.apply_frame_change(which) != old_index
+    }
+
+    /// Sets name of frame at given index, false if out of bounds
+    pub fn set_frame_name(&mut self, index: usize, name: Option<String>) -> bool {
+        if index >= self.frames.len() {
+            return false;
+        }
+
+        self.sync_frame_meta();
+        self.frame_names[index] = name;
+
+        true
+    }
+
+    /// Name of frame at given index if set
+    pub fn frame_name(&self, index: usize) -> Option<&str> {
+        self.frame_names.get(index).and_then(|name| name.as_deref())
+    }
+
+    /// Finds index of first frame with given name
+    pub fn frame_by_name(&self, name: &str) -> Option<usize> {
+        self.frame_names
+            .iter()
+            .take(self.frames.len())
+            .position(|n| n.as_deref() == Some(name))
+    }
+
+    /// Sets duration in milliseconds of frame at given index, false if out of bounds
+    pub fn set_frame_duration(&mut self, index: usize, ms: u16) -> bool {
+        if index >= self.frames.len() {
+            return false;
+        }
+
+        self.sync_frame_meta();
+        self.durations[index] = ms;
+
+        true
+    }
+
+    /// Duration in milliseconds of frame at given index if specified
+    pub fn frame_duration(&self, index: usize) -> Option<u16> {
+        self.durations
+            .get(index)
+            .copied()
+            .filter(|ms| *ms > 0 && index < self.frames.len())
+    }
+
+    // frames are public and can be changed directly, pad/cut metadata to frame count
+    fn sync_frame_meta(&mut self) {
+        self.frame_names.resize(self.frames.len(), None);
+        self.durations.resize(self.frames.len(), 0);
+    }
+
     /// Sets frae to given index, empty Error if out of bounds
     fn set_frame(&mut self, index: usize) -> Result<usize, ()> {
         self.index = if index >= self.frames.len() {
@@ -133,6 +385,44 @@ impl Sprite {
         Ok(self.index)
     }
 
+    /// Sets sprite ID
+    pub fn set_id(&mut self, id: u32) {
+        self.id = Some(id);
+    }
+
+    /// Returns sprite ID, assigning one from *generator* first if missing
+    pub fn ensure_id(&mut self, generator: &mut dyn FnMut() -> u32) -> u32 {
+        match self.id {
+            Some(id) => id,
+            None => {
+                let id = generator();
+                self.id = Some(id);
+                id
+            }
+        }
+    }
+
+    /// Adds *label* with empty value, false if already present
+    pub fn add_label(&mut self, label: &str) -> bool {
+        if self.has_label(label) {
+            return false;
+        }
+
+        self.labels.insert(label.to_owned(), String::new());
+
+        true
+    }
+
+    /// Removes *label* regardless of value, false if not present
+    pub fn remove_label(&mut self, label: &str) -> bool {
+        self.labels.remove(label).is_some()
+    }
+
+    /// Checks if *label* is present regardless of value
+    pub fn has_label(&self, label: &str) -> bool {
+        self.labels.contains_key(label)
+    }
+
     /// Gives a read-only iterator over `Texel`s in given area of this Sprite
     pub fn read_area(&self, area: Bounds) -> impl Iterator<Item = &Texel> {
         self.frame_iter().filter(move |t| area.contains(t.pos))
@@ -142,16 +432,271 @@ impl Sprite {
         self.read_area(Bounds::point(pos)).next()
     }
 
+    /// Texel at given position in current frame if any
+    pub fn texel_at(&self, pos: Position2D) -> Option<&Texel> {
+        self.frame_iter().find(|t| t.pos == pos)
+    }
+
+    /// Symbol at given position in current frame if any
+    pub fn symbol_at(&self, pos: Position2D) -> Option<char> {
+        self.texel_at(pos).map(|t| t.symbol)
+    }
+
+    /// Color according to `ColorMode` at given position in current frame if any
+    pub fn color_at(&self, pos: Position2D, cm: ColorMode) -> Option<u8> {
+        self.texel_at(pos).map(|t| match cm {
+            ColorMode::Bg => t.bg,
+            ColorMode::Fg => t.fg,
+        })
+    }
+
+    /// Mutable texel at given position in current frame if any
+    pub fn texel_at_mut(&mut self, pos: Position2D) -> Option<&mut Texel> {
+        self.frame_iter_mut().find(|t| t.pos == pos)
+    }
+
+    /// Checks if there's a texel at given position in current frame
+    pub fn has_texel_at(&self, pos: Position2D) -> bool {
+        self.frame_iter().any(|t| t.pos == pos)
+    }
+
     /// Copies an area of given frame in the `area: Bounds` as Vec<Texel>
     pub fn copy_area(&self, area: Bounds) -> Texels {
-        let mut result = Texels::new();
-        for texel in self.frame_iter().filter(|t| area.contains(t.pos)) {
-            result.push(texel.moved_from(*area.position()));
+        texels_in_bounds(&self.frames[self.index], area)
+    }
+
+    /// Distinct colors according to `ColorMode` used across all frames, sorted ascending
+    pub fn palette(&self, cm: ColorMode) -> Vec<u8> {
+        let colors: BTreeSet<u8> = self
+            .all_iter()
+            .map(|t| match cm {
+                ColorMode::Bg => t.bg,
+                ColorMode::Fg => t.fg,
+            })
+            .collect();
+
+        colors.into_iter().collect()
+    }
+
+    /// Distinct background and foreground colors used across all frames as (bg, fg)
+    pub fn palette_all(&self) -> (Vec<u8>, Vec<u8>) {
+        (self.palette(ColorMode::Bg), self.palette(ColorMode::Fg))
+    }
+
+    /// Creates a copy of this sprite with all frames cropped to given `Bounds` *area*,
+    /// texel positions are made relative to the area position
+    pub fn crop(&self, area: Bounds) -> Sprite {
+        let mut result = self.clone();
+
+        for (frame, source) in result.frames.iter_mut().zip(self.frames.iter()) {
+            *frame = texels_in_bounds(source, area);
+        }
+
+        result
+    }
+
+    /// Creates a single frame sprite compositing all frames as layers, first frame
+    /// at the bottom. Transparent texels do not overwrite texels below them.
+    pub fn flatten_transparent(&self) -> Sprite {
+        self.flatten(true)
+    }
+
+    /// Creates a single frame sprite layering all frames, first frame at the bottom.
+    /// Unlike `flatten_transparent` the last frame's texel at each position wins
+    /// even when blank.
+    pub fn merge_frames_into_one(&self) -> Sprite {
+        self.flatten(false)
+    }
+
+    // layers all frames into a single frame sprite keeping id and labels
+    fn flatten(&self, transparent: bool) -> Sprite {
+        let mut flat = Texels::new();
+
+        for texel in self.all_iter() {
+            if let Some(existing) = flat.iter_mut().find(|t| t.pos == texel.pos) {
+                *existing = if transparent {
+                    existing.overlay(texel)
+                } else {
+                    texel.clone()
+                };
+            } else {
+                flat.push(texel.clone());
+            }
+        }
+
+        let mut result = Sprite::from_texels(flat);
+        result.id = self.id;
+        result.labels = self.labels.clone();
+
+        result
+    }
+
+    /// Creates a copy of this sprite with outer rows and columns of blank
+    /// (transparent or absent) texels removed, each frame is trimmed independently
+    /// and moved to start at 0, 0
+    pub fn trim(&self) -> Sprite {
+        let mut result = self.clone();
+
+        for frame in result.frames.iter_mut() {
+            let mut content = frame.iter().filter(|t| !t.is_transparent());
+            let first = match content.next() {
+                Some(t) => t.pos,
+                None => {
+                    frame.clear();
+                    continue;
+                }
+            };
+
+            let (min, max) = content.fold((first, first), |(min, max), t| {
+                (
+                    Position2D::from_xy(
+                        std::cmp::min(min.x, t.pos.x),
+                        std::cmp::min(min.y, t.pos.y),
+                    ),
+                    Position2D::from_xy(
+                        std::cmp::max(max.x, t.pos.x),
+                        std::cmp::max(max.y, t.pos.y),
+                    ),
+                )
+            });
+            let area = min.area(max);
+
+            frame.retain(|t| area.contains(t.pos));
+            for t in frame.iter_mut() {
+                t.pos -= min;
+            }
+        }
+
+        result
+    }
+
+    /// Removes outer rows and columns of blank (transparent or absent) texels
+    /// across all frames jointly so they stay aligned, returns the new `Bounds`
+    pub fn trim_in_place(&mut self) -> Bounds {
+        let mut content = self.all_iter().filter(|t| !t.is_transparent());
+        let area = content.next().map(|first| {
+            let (min, max) = content.fold((first.pos, first.pos), |(min, max), t| {
+                (
+                    Position2D::from_xy(
+                        std::cmp::min(min.x, t.pos.x),
+                        std::cmp::min(min.y, t.pos.y),
+                    ),
+                    Position2D::from_xy(
+                        std::cmp::max(max.x, t.pos.x),
+                        std::cmp::max(max.y, t.pos.y),
+                    ),
+                )
+            });
+
+            min.area(max)
+        });
+
+        for frame in self.frames.iter_mut() {
+            match area {
+                Some(area) => frame.retain(|t| area.contains(t.pos)),
+                None => frame.clear(),
+            }
+        }
+
+        self.calculate_bounds()
+    }
+
+    /// Creates a copy of this sprite with a border of *fill* texels added around
+    /// each frame, sizes given in texels for each side
+    pub fn pad(&self, top: u16, right: u16, bottom: u16, left: u16, fill: Texel) -> Sprite {
+        let dim = self.max_frame_dimension();
+        let offset = Position2D::from_xy(i32::from(left), i32::from(top));
+        let inner = Bounds::Free(offset, dim);
+        let outer = Bounds::Free(
+            Position2D::default(),
+            Dimension::from_wh(
+                dim.w.saturating_add(left).saturating_add(right),
+                dim.h.saturating_add(top).saturating_add(bottom),
+            ),
+        );
+        let mut result = self.clone();
+
+        for frame in result.frames.iter_mut() {
+            for t in frame.iter_mut() {
+                t.pos += offset;
+            }
+
+            for pos in outer.into_iter().filter(|p| !inner.contains(*p)) {
+                let mut padding = fill.clone();
+                padding.pos = pos;
+                frame.push(padding);
+            }
+        }
+
+        result
+    }
+
+    /// Creates a new sprite with *other* placed to the right of this one,
+    /// shorter sprite's frames get cycled to match frame counts
+    pub fn concatenate_right(&self, other: &Sprite) -> Sprite {
+        let offset = Position2D::from_xy(i32::from(self.max_frame_dimension().w), 0);
+
+        self.composite(other, offset, false)
+    }
+
+    /// Creates a new sprite with *other* placed below this one,
+    /// shorter sprite's frames get cycled to match frame counts
+    pub fn concatenate_below(&self, other: &Sprite) -> Sprite {
+        let offset = Position2D::from_xy(0, i32::from(self.max_frame_dimension().h));
+
+        self.composite(other, offset, false)
+    }
+
+    /// Creates a new sprite with *other* layered on top of this one at *offset*,
+    /// transparent texels of *other* reveal this sprite's texels below them.
+    /// Shorter sprite's frames get cycled to match frame counts. Result is normalized
+    /// to start at 0, 0
+    pub fn overlay(&self, other: &Sprite, offset: Position2D) -> Sprite {
+        self.composite(other, offset, true)
+    }
+
+    // layers other sprite on top of this one at offset, cycling frames as needed
+    fn composite(&self, other: &Sprite, offset: Position2D, transparent: bool) -> Sprite {
+        let count = std::cmp::max(self.frames.len(), other.frames.len());
+        let mut frames = Vec::with_capacity(count);
+
+        for i in 0..count {
+            let mut frame = self.frames[i % self.frames.len()].clone();
+
+            for texel in &other.frames[i % other.frames.len()] {
+                let mut moved = texel.clone();
+                moved.pos += offset;
+
+                if let Some(existing) = frame.iter_mut().find(|t| t.pos == moved.pos) {
+                    *existing = if transparent {
+                        existing.overlay(&moved)
+                    } else {
+                        moved
+                    };
+                } else {
+                    frame.push(moved);
+                }
+            }
+
+            frames.push(frame);
         }
 
+        let mut result = Sprite::with_frames(frames);
+        result.calculate_bounds(); // normalize negative offsets
+
         result
     }
 
+    /// Symbol frequency in current frame
+    pub fn count_symbols(&self) -> HashMap<char, usize> {
+        count_symbols(self.frame_iter())
+    }
+
+    /// Symbol frequency accumulated across all frames
+    pub fn count_symbols_all_frames(&self) -> HashMap<char, usize> {
+        count_symbols(self.all_iter())
+    }
+
     /// Iterator for list of `Texel` for all frames in the sprite
     pub fn all_iter(&self) -> impl Iterator<Item = &Texel> {
         self.frames.iter().flatten()
@@ -162,6 +707,173 @@ impl Sprite {
         self.frames.iter_mut().flatten()
     }
 
+    /// Creates a single frame sprite from frame at given index keeping id and labels,
+    /// None if out of bounds
+    pub fn clone_frame(&self, index: usize) -> Option<Sprite> {
+        let texels = self.frames.get(index)?.clone();
+        let mut result = Sprite::from_texels(texels);
+        result.id = self.id;
+        result.labels = self.labels.clone();
+        result.frame_names[0] = self.frame_names.get(index).cloned().flatten();
+        result.durations[0] = self.durations.get(index).copied().unwrap_or(0);
+
+        Some(result)
+    }
+
+    /// Creates a single frame sprite from current frame keeping id and labels
+    pub fn clone_current_frame(&self) -> Sprite {
+        self.clone_frame(self.index).unwrap_or_default()
+    }
+
+    /// Frame texels at given index, None if out of bounds
+    pub fn frame(&self, index: usize) -> Option<&Texels> {
+        self.frames.get(index)
+    }
+
+    /// Mutable frame texels at given index, None if out of bounds
+    pub fn frame_mut(&mut self, index: usize) -> Option<&mut Texels> {
+        self.frames.get_mut(index)
+    }
+
+    /// Largest width and height of any single frame measured from sprite origin,
+    /// fits every frame without clipping, zero if all frames are empty
+    pub fn max_frame_dimension(&self) -> Dimension {
+        self.frames
+            .iter()
+            .filter(|frame| !frame.is_empty())
+            .map(|frame| Dimension::for_area(Position2D::default(), max_pos(frame.iter())))
+            .fold(Dimension::default(), |max, dim| Dimension {
+                w: std::cmp::max(max.w, dim.w),
+                h: std::cmp::max(max.h, dim.h),
+            })
+    }
+
+    /// Pads all frames with blank texels so each covers the same area of
+    /// `max_frame_dimension`, existing texels are kept. Returns true if any were added
+    pub fn normalize_frame_dimensions(&mut self) -> bool {
+        let area = Bounds::Free(Position2D::default(), self.max_frame_dimension());
+        let mut changed = false;
+
+        for frame in self.frames.iter_mut() {
+            for pos in area {
+                if !frame.iter().any(|t| t.pos == pos) {
+                    frame.push(Texel {
+                        pos,
+                        ..Texel::default()
+                    });
+                    changed = true;
+                }
+            }
+        }
+
+        changed
+    }
+
+    /// Tight bounds of texels in frame at given index without normalizing,
+    /// empty bounds for empty frame, None if out of bounds
+    pub fn frame_bounds(&self, index: usize) -> Option<Bounds> {
+        let frame = self.frames.get(index)?;
+        let first = match frame.first() {
+            Some(t) => t.pos,
+            None => return Some(Bounds::empty()),
+        };
+
+        let (min, max) = frame.iter().fold((first, first), |(min, max), t| {
+            (
+                Position2D::from_xy(std::cmp::min(min.x, t.pos.x), std::cmp::min(min.y, t.pos.y)),
+                Position2D::from_xy(std::cmp::max(max.x, t.pos.x), std::cmp::max(max.y, t.pos.y)),
+            )
+        });
+
+        Some(Bounds::Free(min, Dimension::for_area(min, max)))
+    }
+
+    /// Content hash of frame at given index independent of texel order, None if out
+    /// of bounds. Based on `DefaultHasher` so values may differ between Rust releases
+    /// and must not be persisted, equal hashes don't guarantee equal contents
+    pub fn frame_hash(&self, index: usize) -> Option<u64> {
+        let mut texel_hashes: Vec<u64> = self
+            .frames
+            .get(index)?
+            .iter()
+            .map(|t| {
+                let mut hasher = DefaultHasher::new();
+                t.hash(&mut hasher);
+                hasher.finish()
+            })
+            .collect();
+        texel_hashes.sort_unstable();
+
+        let mut hasher = DefaultHasher::new();
+        texel_hashes.hash(&mut hasher);
+
+        Some(hasher.finish())
+    }
+
+    ///
+    /// Calculates texels changed between frames *a* and *b*. Consists of texels in *b*
+    /// that differ from or are missing in *a* and blank texels for positions only in *a*.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Texels>` - changed texels, None if either index is out of bounds
+    ///
+    pub fn frame_diff(&self, a: usize, b: usize) -> Option<Texels> {
+        let (from, to) = (self.frames.get(a)?, self.frames.get(b)?);
+        let mut result = Texels::new();
+
+        for texel in to {
+            if !from.iter().any(|t| t == texel) {
+                result.push(texel.clone());
+            }
+        }
+
+        for texel in from {
+            if !to.iter().any(|t| t.pos == texel.pos) {
+                result.push(Texel {
+                    pos: texel.pos,
+                    ..Texel::default()
+                });
+            }
+        }
+
+        Some(result)
+    }
+
+    ///
+    /// Copies texels of frames selected by *which* sorted in row-major order for
+    /// deterministic output. `Next` and `Previous` are relative to the current frame
+    /// and clamped like `apply_frame_change`.
+    ///
+    /// # Returns
+    ///
+    /// * `Texels` - sorted texels, frames concatenated in order for `Which::All`,
+    ///   empty if index is out of bounds
+    ///
+    pub fn sorted_texels(&self, which: Which<usize>) -> Texels {
+        let last = std::cmp::max(self.frames.len(), 1) - 1;
+        let selected = match which {
+            Which::All => &self.frames[..],
+            Which::Next => self.frames_at(std::cmp::min(self.index + 1, last)),
+            Which::Previous => self.frames_at(std::cmp::max(self.index, 1) - 1),
+            Which::At(index) => self.frames_at(index),
+        };
+
+        let mut result = Texels::new();
+        for frame in selected {
+            let mut sorted = frame.clone();
+            sorted.sort_by_key(|t| (t.pos.y, t.pos.x));
+            result.append(&mut sorted);
+        }
+
+        result
+    }
+
+    // single frame slice at given index, empty if out of bounds
+    fn frames_at(&self, index: usize) -> &[Texels] {
+        self.frames.get(index..=index).unwrap_or(&[])
+    }
+
     /// Iterator over current frame's list of `Texel`
     pub fn frame_iter(&self) -> impl Iterator<Item = &Texel> {
         self.frames[self.index].iter()
@@ -211,8 +923,15 @@ impl Sprite {
 
     /// Creates a sprite from list of texels, single frame
     pub fn from_texels(texels: Texels) -> Sprite {
-        Sprite {
-            frames: vec![texels],
+        Sprite::with_frames(vec![texels])
+    }
+
+    // sprite with given frames and matching empty frame metadata
+    fn with_frames(frames: Vec<Texels>) -> Sprite {
+        Sprite {
+            frame_names: vec![None; frames.len()],
+            durations: vec![0; frames.len()],
+            frames,
             index: 0,
             id: None,
             labels: HashMap::new(),
@@ -235,23 +954,136 @@ impl Sprite {
 
     /// Applies *symbol* with *bg/fg* color combination in given `Bounds` *area*
     pub fn apply_symbol(&mut self, symbol: char, bg: u8, fg: u8, area: Bounds) -> Bounds {
+        self.apply_symbol_in_frame(symbol, bg, fg, area);
+
+        self.calculate_bounds()
+    }
+
+    // apply_symbol without bounds calculation, true if any texel changed
+    fn apply_symbol_in_frame(&mut self, symbol: char, bg: u8, fg: u8, area: Bounds) -> bool {
+        let texel = |pos| Texel {
+            symbol,
+            bg,
+            fg,
+            pos,
+            styles: SymbolStyles::new(),
+        };
+        let frame = &mut self.frames[self.index];
+        let changed = frame
+            .iter()
+            .any(|t| area.contains(t.pos) && *t != texel(t.pos))
+            || area
+                .into_iter()
+                .any(|pos| !frame.iter().any(|t| t.pos == pos));
+
         // remove texels in bounds
-        self.frames[self.index].retain(|t| !area.contains(t.pos));
+        frame.retain(|t| !area.contains(t.pos));
 
         // re-add them with new setup
         for pos in area.into_iter() {
-            self.frames[self.index].push(Texel {
-                symbol,
-                bg,
-                fg,
+            frame.push(texel(pos));
+        }
+
+        changed
+    }
+
+    /// Draws a line of *symbol* with *fg/bg* colors between two points in current frame
+    /// using Bresenham's algorithm, both end points included
+    pub fn draw_line(
+        &mut self,
+        from: Position2D,
+        to: Position2D,
+        symbol: char,
+        fg: u8,
+        bg: u8,
+    ) -> Bounds {
+        self.line(from, to, symbol, fg, bg);
+
+        self.calculate_bounds()
+    }
+
+    /// Draws outline of given `Bounds` *area* with *symbol* and *fg/bg* colors in current frame
+    pub fn draw_rect(&mut self, area: Bounds, symbol: char, fg: u8, bg: u8) -> Bounds {
+        if !area.dimension().is_empty() {
+            let top_left = *area.position();
+            let top_right = Position2D::from_xy(area.right(), top_left.y);
+            let bottom_left = Position2D::from_xy(top_left.x, area.bottom());
+            let bottom_right = Position2D::from_xy(area.right(), area.bottom());
+
+            self.line(top_left, top_right, symbol, fg, bg);
+            self.line(top_right, bottom_right, symbol, fg, bg);
+            self.line(bottom_right, bottom_left, symbol, fg, bg);
+            self.line(bottom_left, top_left, symbol, fg, bg);
+        }
+
+        self.calculate_bounds()
+    }
+
+    /// Writes text *s* starting at position *at* in current frame with given colors
+    /// and styles overwriting existing texels, newlines move to the next row
+    pub fn draw_text(
+        &mut self,
+        s: &str,
+        at: Position2D,
+        fg: u8,
+        bg: u8,
+        styles: SymbolStyles,
+    ) -> Bounds {
+        let mut pos = at;
+
+        for c in s.chars() {
+            if c == '\n' {
+                pos = Position2D::from_xy(at.x, pos.y + 1);
+                continue;
+            }
+
+            self.set_texel(Texel {
                 pos,
-                styles: SymbolStyles::new(),
+                symbol: c,
+                fg,
+                bg,
+                styles,
             });
+            pos.x += 1;
         }
 
         self.calculate_bounds()
     }
 
+    // bresenham line without bounds recalculation
+    fn line(&mut self, from: Position2D, to: Position2D, symbol: char, fg: u8, bg: u8) {
+        let dx = (to.x - from.x).abs();
+        let dy = -(to.y - from.y).abs();
+        let sx = if from.x < to.x { 1 } else { -1 };
+        let sy = if from.y < to.y { 1 } else { -1 };
+        let mut err = dx + dy;
+        let mut pos = from;
+
+        loop {
+            self.set_texel(Texel {
+                pos,
+                symbol,
+                fg,
+                bg,
+                styles: SymbolStyles::new(),
+            });
+
+            if pos == to {
+                break;
+            }
+
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                pos.x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                pos.y += sy;
+            }
+        }
+    }
+
     /// Applies *texels* starting at given *pos* `Position2D`
     pub fn apply_texels(&mut self, texels: Texels, pos: Position2D) -> Bounds {
         for texel in texels.into_iter() {
@@ -271,8 +1103,229 @@ impl Sprite {
         self.calculate_bounds()
     }
 
+    /// Sets *texel* in current frame replacing existing one at the same position
+    pub fn set_texel(&mut self, texel: Texel) {
+        if let Some(existing) = self.texel_at_mut(texel.pos) {
+            *existing = texel;
+        } else {
+            self.frames[self.index].push(texel);
+        }
+    }
+
+    /// Removes texel at given position in current frame, false if none was found
+    pub fn remove_texel_at(&mut self, pos: Position2D) -> bool {
+        let frame = &mut self.frames[self.index];
+
+        if let Some(i) = frame.iter().position(|t| t.pos == pos) {
+            frame.remove(i);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Drops earlier texels sharing a position with a later one in all frames,
+    /// order of remaining texels is preserved
+    pub fn dedup_positions(&mut self) {
+        for frame in self.frames.iter_mut() {
+            let mut seen = HashSet::new();
+            let mut kept: Texels = frame
+                .drain(..)
+                .rev()
+                .filter(|t| seen.insert(t.pos))
+                .collect();
+
+            kept.reverse();
+            *frame = kept;
+        }
+    }
+
+    /// Shifts all texels in current frame by *offset*
+    pub fn translate_frame(&mut self, offset: Position2D) -> Bounds {
+        for t in self.frame_iter_mut() {
+            t.pos += offset;
+        }
+
+        self.calculate_bounds()
+    }
+
+    /// Shifts all texels in all frames by *offset*
+    pub fn translate_all_frames(&mut self, offset: Position2D) -> Bounds {
+        for t in self.all_iter_mut() {
+            t.pos += offset;
+        }
+
+        self.calculate_bounds()
+    }
+
+    /// Scrolls texels in current frame by *by* within the sprite's dimension, texels
+    /// leaving an edge reappear on the opposite one if *wrap* otherwise get dropped
+    pub fn shift(&mut self, by: Position2D, wrap: bool) {
+        let dim = self.max_frame_dimension();
+        let area = Bounds::Free(Position2D::default(), dim);
+        let (w, h) = (i32::from(dim.w), i32::from(dim.h));
+
+        for t in self.frame_iter_mut() {
+            t.pos += by;
+            if wrap {
+                t.pos = Position2D::from_xy(t.pos.x.rem_euclid(w), t.pos.y.rem_euclid(h));
+            }
+        }
+
+        if !wrap {
+            self.frames[self.index].retain(|t| area.contains(t.pos));
+        }
+    }
+
+    /// Rotates current frame by 90 degrees, only positions are transformed
+    pub fn rotate_90(&mut self, clockwise: bool) -> Bounds {
+        let max = max_pos(self.frame_iter());
+        rotate_texels(self.frame_iter_mut(), max, clockwise);
+
+        self.calculate_bounds()
+    }
+
+    /// Rotates all frames by 90 degrees, only positions are transformed
+    pub fn rotate_all_frames(&mut self, clockwise: bool) -> Bounds {
+        let max = max_pos(self.all_iter());
+        rotate_texels(self.all_iter_mut(), max, clockwise);
+
+        self.calculate_bounds()
+    }
+
+    /// Flips current frame horizontally, mirroring texels along vertical axis
+    pub fn flip_horizontal(&mut self) -> Bounds {
+        let max = max_pos(self.frame_iter());
+        for t in self.frame_iter_mut() {
+            t.pos.x = max.x - t.pos.x;
+        }
+
+        self.calculate_bounds()
+    }
+
+    /// Flips current frame vertically, mirroring texels along horizontal axis
+    pub fn flip_vertical(&mut self) -> Bounds {
+        let max = max_pos(self.frame_iter());
+        for t in self.frame_iter_mut() {
+            t.pos.y = max.y - t.pos.y;
+        }
+
+        self.calculate_bounds()
+    }
+
+    /// Flips all frames horizontally, mirroring texels along vertical axis
+    pub fn flip_horizontal_all_frames(&mut self) -> Bounds {
+        let max = max_pos(self.all_iter());
+        for t in self.all_iter_mut() {
+            t.pos.x = max.x - t.pos.x;
+        }
+
+        self.calculate_bounds()
+    }
+
+    /// Flips all frames vertically, mirroring texels along horizontal axis
+    pub fn flip_vertical_all_frames(&mut self) -> Bounds {
+        let max = max_pos(self.all_iter());
+        for t in self.all_iter_mut() {
+            t.pos.y = max.y - t.pos.y;
+        }
+
+        self.calculate_bounds()
+    }
+
+    /// Creates a copy of this sprite upscaled by *factor*, each texel becoming a
+    /// *factor* x *factor* block. Factor of 0 results in empty frames.
+    pub fn scale(&self, factor: u16) -> Sprite {
+        let f = i32::from(factor);
+        let mut result = self.clone();
+
+        for (frame, source) in result.frames.iter_mut().zip(self.frames.iter()) {
+            *frame = Texels::with_capacity(source.len() * usize::from(factor).pow(2));
+
+            for texel in source {
+                for y in 0..f {
+                    for x in 0..f {
+                        let mut scaled = texel.clone();
+                        scaled.pos = Position2D::from_xy(texel.pos.x * f + x, texel.pos.y * f + y);
+                        frame.push(scaled);
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    ///
+    /// Replaces all texels 4-connected to *start* sharing its symbol with *replacement*.
+    /// If there's no texel at *start* all connected empty cells get filled instead.
+    /// Fill is constrained to the sprite's area, start outside of it is a no-op.
+    ///
+    pub fn flood_fill(&mut self, start: Position2D, replacement: Texel) {
+        // actual extents as texels don't need to start at 0, 0
+        let area = self.content_bounds();
+        if !area.contains(start) {
+            return;
+        }
+
+        // Dimension::size multiplies in u16 which overflows for large sprites
+        let origin = *area.position();
+        let w = usize::from(area.dimension().w);
+        let size = w * usize::from(area.dimension().h);
+        let cell = |pos: Position2D| (pos.y - origin.y) as usize * w + (pos.x - origin.x) as usize;
+        let frame = &mut self.frames[self.index];
+        let mut grid: Vec<Option<usize>> = vec![None; size];
+
+        for (i, t) in frame.iter().enumerate() {
+            if area.contains(t.pos) {
+                grid[cell(t.pos)] = Some(i);
+            }
+        }
+
+        let target = grid[cell(start)].map(|i| frame[i].symbol);
+        let mut visited = vec![false; size];
+        let mut queue = VecDeque::new();
+        let mut added = Texels::new();
+
+        visited[cell(start)] = true;
+        queue.push_back(start);
+
+        while let Some(pos) = queue.pop_front() {
+            let existing = grid[cell(pos)];
+            if existing.map(|i| frame[i].symbol) != target {
+                continue;
+            }
+
+            let mut filled = replacement.clone();
+            filled.pos = pos;
+            match existing {
+                Some(i) => frame[i] = filled,
+                None => added.push(filled),
+            }
+
+            for (x, y) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+                let next = pos + Position2D::from_xy(x, y);
+
+                if area.contains(next) && !visited[cell(next)] {
+                    visited[cell(next)] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        frame.append(&mut added);
+    }
+
     /// Applies *color* according to `ColorMode` in the given `Bounds` *area*
     pub fn apply_color(&mut self, cm: ColorMode, color: u8, area: Bounds) -> bool {
+        let changed = self.apply_color_in_frame(cm, color, area);
+        self.calculate_bounds();
+
+        changed
+    }
+
+    // apply_color without bounds calculation
+    fn apply_color_in_frame(&mut self, cm: ColorMode, color: u8, area: Bounds) -> bool {
         let mut changed = false;
         let mut new_texels = Vec::with_capacity(self.frames[self.index].capacity());
 
@@ -301,12 +1354,162 @@ impl Sprite {
             }
         }
 
-        // apply the new texel list
-        self.apply_texels(new_texels, Position2D::from_xy(0, 0));
+        // add the missing texels, none of them share a position with existing ones
+        self.frames[self.index].append(&mut new_texels);
+
+        changed
+    }
+
+    ///
+    /// Applies a linear gradient from *from_color* to *to_color* according to `ColorMode`
+    /// in the given `Bounds` *area*, left to right if *horizontal* otherwise top to bottom.
+    /// Missing texels get added as blank "background" texels, true if any texel got
+    /// added or changed color.
+    ///
+    pub fn apply_gradient(
+        &mut self,
+        area: Bounds,
+        from_color: u8,
+        to_color: u8,
+        cm: ColorMode,
+        horizontal: bool,
+    ) -> bool {
+        let dim = area.dimension();
+        let steps = i32::from(if horizontal { dim.w } else { dim.h }) - 1;
+        let (from, to) = (i32::from(from_color), i32::from(to_color));
+        let mut changed = false;
+
+        for pos in area.into_iter() {
+            let step = if horizontal {
+                pos.x - area.position().x
+            } else {
+                pos.y - area.position().y
+            };
+            let color = if steps > 0 {
+                from + (to - from) * step / steps
+            } else {
+                from
+            };
+            let color = color.clamp(0, 255) as u8;
+
+            if let Some(texel) = self.texel_at_mut(pos) {
+                let existing = match cm {
+                    ColorMode::Bg => &mut texel.bg,
+                    ColorMode::Fg => &mut texel.fg,
+                };
+                if *existing != color {
+                    *existing = color;
+                    changed = true;
+                }
+            } else {
+                let (bg, fg) = match cm {
+                    ColorMode::Bg => (color, DEFAULT_FG_U8),
+                    ColorMode::Fg => (DEFAULT_BG_U8, color),
+                };
+                self.frames[self.index].push(Texel {
+                    pos,
+                    fg,
+                    bg,
+                    styles: SymbolStyles::new(),
+                    symbol: ' ',
+                });
+                changed = true;
+            }
+        }
+        self.calculate_bounds();
+
+        changed
+    }
+
+    /// Applies a linear gradient from *start* to *end* color according to `ColorMode`
+    /// in the given `Bounds` *area* going towards *dir*, see `apply_gradient`
+    pub fn apply_gradient_towards(
+        &mut self,
+        cm: ColorMode,
+        start: u8,
+        end: u8,
+        dir: Direction,
+        area: Bounds,
+    ) -> bool {
+        match dir {
+            Direction::Right => self.apply_gradient(area, start, end, cm, true),
+            Direction::Left => self.apply_gradient(area, end, start, cm, true),
+            Direction::Bottom => self.apply_gradient(area, start, end, cm, false),
+            Direction::Top => self.apply_gradient(area, end, start, cm, false),
+        }
+    }
+
+    /// Applies *color* according to `ColorMode` to existing texels in the given `Bounds` *area*,
+    /// unlike `apply_color` no background texels are added for empty cells
+    pub fn recolor_existing(&mut self, cm: ColorMode, color: u8, area: Bounds) -> bool {
+        let mut changed = false;
+
+        for texel in self.frame_iter_mut().filter(|t| area.contains(t.pos)) {
+            match cm {
+                ColorMode::Bg => texel.bg = color,
+                ColorMode::Fg => texel.fg = color,
+            }
+            changed = true;
+        }
+
+        changed
+    }
+
+    /// Replaces *from* color with *to* color according to `ColorMode` in current frame
+    pub fn replace_color(&mut self, cm: ColorMode, from: u8, to: u8) -> bool {
+        let mut changed = false;
+
+        for texel in self.frame_iter_mut() {
+            let color = match cm {
+                ColorMode::Bg => &mut texel.bg,
+                ColorMode::Fg => &mut texel.fg,
+            };
+
+            if *color == from {
+                *color = to;
+                changed = true;
+            }
+        }
+
+        changed
+    }
+
+    /// Replaces *from* symbol with *to* symbol in current frame
+    pub fn replace_symbol(&mut self, from: char, to: char) -> bool {
+        let mut changed = false;
+
+        for texel in self.frame_iter_mut().filter(|t| t.symbol == from) {
+            texel.symbol = to;
+            changed = true;
+        }
+
+        changed
+    }
+
+    /// Replaces *from* symbol with *to* symbol in all frames
+    pub fn replace_symbol_all_frames(&mut self, from: char, to: char) -> bool {
+        let mut changed = false;
+
+        for texel in self.all_iter_mut().filter(|t| t.symbol == from) {
+            texel.symbol = to;
+            changed = true;
+        }
 
         changed
     }
 
+    /// Positions of given *symbol* in current frame sorted by rows
+    pub fn search(&self, symbol: char) -> Vec<Position2D> {
+        let mut result: Vec<Position2D> = self
+            .frame_iter()
+            .filter(|t| t.symbol == symbol)
+            .map(|t| t.pos)
+            .collect();
+        result.sort_by_key(|pos| (pos.y, pos.x));
+
+        result
+    }
+
     /// Applies a single *style* for the given `Bounds` *area*
     pub fn apply_style(&mut self, style: SymbolStyle, area: Bounds) -> bool {
         let mut changed = false;
@@ -324,6 +1527,49 @@ impl Sprite {
         changed
     }
 
+    /// Applies *color* according to `ColorMode` in the given `Bounds` *area* of all frames
+    pub fn apply_color_to_all_frames(&mut self, cm: ColorMode, color: u8, area: Bounds) -> bool {
+        let changed = self.for_all_frames(|sprite| sprite.apply_color_in_frame(cm, color, area));
+        self.calculate_bounds();
+
+        changed
+    }
+
+    /// Applies a single *style* for the given `Bounds` *area* of all frames
+    pub fn apply_style_to_all_frames(&mut self, style: SymbolStyle, area: Bounds) -> bool {
+        self.for_all_frames(|sprite| sprite.apply_style(style, area))
+    }
+
+    /// Applies *symbol* with *bg/fg* color combination in given `Bounds` *area* of all frames
+    pub fn apply_symbol_to_all_frames(
+        &mut self,
+        symbol: char,
+        bg: u8,
+        fg: u8,
+        area: Bounds,
+    ) -> bool {
+        let changed =
+            self.for_all_frames(|sprite| sprite.apply_symbol_in_frame(symbol, bg, fg, area));
+        self.calculate_bounds();
+
+        changed
+    }
+
+    // runs given single frame operation on each frame keeping current index, operations
+    // must not normalize texels as that would shift *area* for the remaining frames
+    fn for_all_frames(&mut self, mut op: impl FnMut(&mut Sprite) -> bool) -> bool {
+        let index = self.index;
+        let mut changed = false;
+
+        for i in 0..self.frames.len() {
+            self.index = i;
+            changed |= op(self);
+        }
+        self.index = index;
+
+        changed
+    }
+
     /// Removes texels in given `Bounds` *area*
     pub fn clear_symbol(&mut self, area: Bounds) -> Option<Bounds> {
         let count = self.frames[self.index].len();
@@ -336,6 +1582,44 @@ impl Sprite {
         None
     }
 
+    /// Removes texels outside of *max* dimension from current frame keeping top-left,
+    /// returns true if any texel was removed
+    pub fn clip_to_max(&mut self, max: Dimension) -> bool {
+        let count = self.frames[self.index].len();
+        self.frames[self.index]
+            .retain(|t| t.pos.x < i32::from(max.w) && t.pos.y < i32::from(max.h));
+
+        count != self.frames[self.index].len()
+    }
+
+    /// Resizes current frame to *new_dim* dropping texels outside of it, empty cells
+    /// inside get filled with copies of *fill* if given
+    pub fn resize(&mut self, new_dim: Dimension, fill: Option<Texel>) {
+        self.clip_to_max(new_dim);
+
+        if let Some(fill) = fill {
+            for pos in Bounds::Free(Position2D::default(), new_dim) {
+                if !self.has_texel_at(pos) {
+                    self.frames[self.index].push(Texel {
+                        pos,
+                        ..fill.clone()
+                    });
+                }
+            }
+        }
+    }
+
+    /// Removes all texels in current frame
+    pub fn clear_frame(&mut self) -> Option<Bounds> {
+        if self.frames[self.index].is_empty() {
+            return None;
+        }
+
+        self.frames[self.index].clear();
+
+        Some(Bounds::empty())
+    }
+
     /// Empty check, true if all frames empty
     pub fn is_empty(&self) -> bool {
         self.frames.is_empty()
@@ -347,6 +1631,32 @@ impl Sprite {
                 .unwrap_or(false)
     }
 
+    /// Bounds of texels in all frames computed on demand, compare values taken
+    /// before and after a mutating method to detect growth or shrinkage
+    pub fn last_bounds(&self) -> Bounds {
+        self.content_bounds()
+    }
+
+    /// Tight bounds of texels in all frames without normalizing, empty bounds if
+    /// there are no texels. See `frame_bounds` for a single frame
+    pub fn content_bounds(&self) -> Bounds {
+        let min = self.all_iter().fold(None, |min: Option<Position2D>, t| {
+            Some(min.map_or(t.pos, |min| {
+                Position2D::from_xy(std::cmp::min(min.x, t.pos.x), std::cmp::min(min.y, t.pos.y))
+            }))
+        });
+
+        match min {
+            Some(min) => Bounds::Free(min, Dimension::for_sprite(self)),
+            None => Bounds::empty(),
+        }
+    }
+
+    /// Normalizes texels to start at 0, 0 returning the offset that was subtracted
+    pub fn trim_returning_offset(&mut self) -> Position2D {
+        *self.calculate_bounds().position()
+    }
+
     // goes through texels so we can calculate dimension and move position if
     // needed. TODO: optimize, we're doing 3 loops here for no good reason
     fn calculate_bounds(&mut self) -> Bounds {
@@ -385,3 +1695,1895 @@ impl Sprite {
         )
     }
 }
+
+fn count_symbols<'a>(texels: impl Iterator<Item = &'a Texel>) -> HashMap<char, usize> {
+    let mut result = HashMap::new();
+
+    for t in texels {
+        *result.entry(t.symbol).or_insert(0) += 1;
+    }
+
+    result
+}
+
+// order independent frame contents comparison
+fn same_content(a: &[Texel], b: &[Texel]) -> bool {
+    a.len() == b.len() && a.iter().all(|t| b.contains(t))
+}
+
+// bottom-right most coordinates of given texels, 0, 0 if empty
+fn max_pos<'a>(texels: impl Iterator<Item = &'a Texel>) -> Position2D {
+    texels.fold(Position2D::default(), |max, t| Position2D {
+        x: std::cmp::max(max.x, t.pos.x),
+        y: std::cmp::max(max.y, t.pos.y),
+    })
+}
+
+fn rotate_texels<'a>(
+    texels: impl Iterator<Item = &'a mut Texel>,
+    max: Position2D,
+    clockwise: bool,
+) {
+    for t in texels {
+        t.pos = if clockwise {
+            Position2D::from_xy(max.y - t.pos.y, t.pos.x)
+        } else {
+            Position2D::from_xy(t.pos.y, max.x - t.pos.x)
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::texels_from_str;
+    use crate::TexelBuilder;
+
+    fn symbols(texels: &[Texel]) -> String {
+        let mut sorted = texels.to_vec();
+        sorted.sort_by_key(|t| (t.pos.y, t.pos.x));
+        sorted.iter().map(|t| t.symbol).collect()
+    }
+
+    fn sprite(s: &str) -> Sprite {
+        Sprite::from_texels(texels_from_str(s, Position2D::default()))
+    }
+
+    #[test]
+    fn frame_names_set_and_lookup() {
+        let mut s = sprite("a");
+        s.new_frame();
+        s.new_frame();
+
+        assert!(s.set_frame_name(1, Some("walk".to_owned())));
+        assert!(!s.set_frame_name(3, Some("idle".to_owned())));
+        assert_eq!(s.frame_name(1), Some("walk"));
+        assert_eq!(s.frame_name(0), None);
+        assert_eq!(s.frame_by_name("walk"), Some(1));
+        assert_eq!(s.frame_by_name("idle"), None);
+        assert_eq!(s.frame_names.len(), s.frame_count());
+    }
+
+    #[test]
+    fn frame_names_follow_frame_changes() {
+        let mut s = sprite("a");
+        s.new_frame();
+        s.set_frame_name(0, Some("first".to_owned()));
+        s.delete_frame();
+
+        assert_eq!(s.frame_count(), 1);
+        assert_eq!(s.frame_by_name("first"), Some(0));
+    }
+
+    #[test]
+    fn frame_names_empty_for_older_sprites() {
+        let old = SpriteV1 {
+            frames: vec![Texels::new(), Texels::new()],
+            index: 1,
+        };
+        let s = Sprite::from(old);
+
+        assert_eq!(s.frame_names, vec![None, None]);
+        assert_eq!(s.index, 1);
+    }
+
+    #[test]
+    fn set_frame_name_keeps_sprite_equal() {
+        let mut s = Sprite::default();
+        s.set_frame_name(0, None);
+
+        assert_eq!(s, Sprite::default());
+    }
+
+    #[test]
+    fn frame_names_missing_entries_compare_as_none() {
+        let mut s = sprite("a");
+        s.frames.push(Texels::new());
+        let mut other = s.clone();
+        other.frame_names.clear();
+
+        assert_eq!(s, other);
+        s.set_frame_name(1, Some("walk".to_owned()));
+        assert_ne!(s, other);
+        other.frame_names = vec![None, Some("walk".to_owned()), Some("stale".to_owned())];
+        assert_eq!(s, other);
+    }
+
+    #[test]
+    fn frame_names_empty_for_v2_sprites() {
+        let old = SpriteV2 {
+            frames: vec![Texels::new(), Texels::new()],
+            index: 1,
+            id: Some(3),
+            labels: HashMap::new(),
+        };
+        let s = Sprite::from(old);
+
+        assert_eq!(s.frame_names, vec![None, None]);
+        assert_eq!(s.index, 1);
+        assert_eq!(s.id, Some(3));
+    }
+
+    #[test]
+    fn replace_color_on_some_texels() {
+        let mut s = sprite("abc");
+        s.frames[0][1].fg = 1;
+
+        assert!(s.replace_color(ColorMode::Fg, 1, 2));
+        assert_eq!(s.frames[0][1].fg, 2);
+        assert_eq!(s.frames[0][0].fg, DEFAULT_FG_U8);
+        assert_eq!(s.frames[0].len(), 3);
+    }
+
+    #[test]
+    fn replace_color_missing_color() {
+        let mut s = sprite("abc");
+
+        assert!(!s.replace_color(ColorMode::Bg, 99, 2));
+        assert_eq!(s, sprite("abc"));
+    }
+
+    #[test]
+    fn texel_at_current_frame() {
+        let mut s = sprite("ab");
+        s.frames.push(Texels::new());
+        s.apply_frame_change(Which::At(0));
+        let pos = Position2D::from_xy(1, 0);
+
+        assert_eq!(s.texel_at(pos).map(|t| t.symbol), Some('b'));
+        assert!(s.has_texel_at(pos));
+        assert!(!s.has_texel_at(Position2D::from_xy(2, 0)));
+
+        s.texel_at_mut(pos).unwrap().symbol = 'x';
+        assert_eq!(s.texel_at(pos).map(|t| t.symbol), Some('x'));
+
+        s.apply_frame_change(Which::At(1));
+        assert!(s.texel_at(pos).is_none());
+        assert!(s.texel_at_mut(pos).is_none());
+    }
+
+    #[test]
+    fn palette_across_frames() {
+        let mut s = sprite("ab");
+        s.frames[0][0].fg = 5;
+        s.frames[0][1].fg = 3;
+        s.new_frame();
+        s.frames[1][0].fg = 9;
+        s.frames[1][1].bg = 1;
+
+        assert_eq!(s.palette(ColorMode::Fg), vec![3, 5, 9]);
+        assert_eq!(s.palette(ColorMode::Bg), vec![1, DEFAULT_BG_U8]);
+        assert_eq!(s.palette_all(), (vec![1, DEFAULT_BG_U8], vec![3, 5, 9]));
+    }
+
+    #[test]
+    fn set_texel_replaces_or_adds() {
+        let mut s = sprite("ab");
+        let replaced = Texel {
+            pos: Position2D::from_xy(1, 0),
+            symbol: 'x',
+            ..Texel::default()
+        };
+        let added = Texel {
+            pos: Position2D::from_xy(3, 1),
+            symbol: 'y',
+            ..Texel::default()
+        };
+
+        s.set_texel(replaced);
+        s.set_texel(added);
+
+        assert_eq!(s.frames[0].len(), 3);
+        assert_eq!(
+            s.texel_at(Position2D::from_xy(1, 0)).map(|t| t.symbol),
+            Some('x')
+        );
+        assert_eq!(
+            s.texel_at(Position2D::from_xy(3, 1)).map(|t| t.symbol),
+            Some('y')
+        );
+    }
+
+    #[test]
+    fn remove_texel_at_position() {
+        let mut s = sprite("ab");
+
+        assert!(s.remove_texel_at(Position2D::from_xy(0, 0)));
+        assert!(!s.remove_texel_at(Position2D::from_xy(0, 0)));
+        assert_eq!(symbols(&s.frames[0]), "b");
+    }
+
+    #[test]
+    fn clear_frame_populated() {
+        let mut s = sprite("ab");
+
+        assert_eq!(s.clear_frame(), Some(Bounds::empty()));
+        assert!(s.frames[0].is_empty());
+    }
+
+    #[test]
+    fn clear_frame_already_empty() {
+        let mut s = Sprite::default();
+
+        assert_eq!(s.clear_frame(), None);
+    }
+
+    fn positions(texels: &[Texel]) -> Vec<(char, i32, i32)> {
+        let mut result: Vec<(char, i32, i32)> = texels
+            .iter()
+            .map(|t| (t.symbol, t.pos.x, t.pos.y))
+            .collect();
+        result.sort_unstable();
+
+        result
+    }
+
+    #[test]
+    fn translate_frame_preserves_relative_positions() {
+        let mut s = sprite("ab\ncd");
+        let before = positions(&s.frames[0]);
+
+        s.translate_frame(Position2D::from_xy(3, 2));
+        assert_eq!(
+            positions(&s.frames[0]),
+            vec![('a', 0, 0), ('b', 1, 0), ('c', 0, 1), ('d', 1, 1)]
+        );
+        assert_eq!(positions(&s.frames[0]), before);
+    }
+
+    #[test]
+    fn translate_frame_negative_offset_normalizes() {
+        let mut s = sprite("ab");
+        s.frames.insert(s.index + 1, Texels::new());
+        s.index += 1;
+        s.frames[1] = texels_from_str("c", Position2D::from_xy(2, 0));
+        s.apply_frame_change(Which::At(0));
+
+        let bounds = s.translate_frame(Position2D::from_xy(-1, 0));
+        assert_eq!(*bounds.position(), Position2D::from_xy(-1, 0));
+        assert_eq!(positions(&s.frames[0]), vec![('a', 0, 0), ('b', 1, 0)]);
+        assert_eq!(positions(&s.frames[1]), vec![('c', 3, 0)]);
+    }
+
+    #[test]
+    fn translate_all_frames_keeps_frames_aligned() {
+        let mut s = sprite("ab");
+        s.new_frame();
+
+        s.translate_all_frames(Position2D::from_xy(-2, 4));
+        assert_eq!(positions(&s.frames[0]), positions(&s.frames[1]));
+        assert_eq!(positions(&s.frames[0]), vec![('a', 0, 0), ('b', 1, 0)]);
+    }
+
+    #[test]
+    fn trim_returning_offset_matches_shift() {
+        let mut s = Sprite::from_texels(texels_from_str("ab", Position2D::from_xy(3, 2)));
+
+        assert_eq!(s.trim_returning_offset(), Position2D::from_xy(3, 2));
+        assert_eq!(s, sprite("ab"));
+        assert_eq!(s.trim_returning_offset(), Position2D::default());
+    }
+
+    #[test]
+    fn insert_blank_frame_at_start() {
+        let mut s = sprite("a");
+        s.insert_blank_frame(0);
+
+        assert_eq!(s.frame_count(), 2);
+        assert_eq!(s.index, 0);
+        assert!(s.frames[0].is_empty());
+        assert_eq!(symbols(&s.frames[1]), "a");
+    }
+
+    #[test]
+    fn insert_blank_frame_in_middle() {
+        let mut s = sprite("a");
+        s.new_frame();
+        s.insert_blank_frame(1);
+
+        assert_eq!(s.frame_count(), 3);
+        assert_eq!(s.index, 1);
+        assert!(s.frames[1].is_empty());
+    }
+
+    #[test]
+    fn insert_blank_frame_past_end_clamps() {
+        let mut s = sprite("a");
+        s.insert_blank_frame(10);
+
+        assert_eq!(s.frame_count(), 2);
+        assert_eq!(s.index, 1);
+        assert!(s.frames[1].is_empty());
+        assert_eq!(s.frame_names.len(), 2);
+    }
+
+    #[test]
+    fn rotate_90_swaps_dimension() {
+        let mut s = sprite("abcd\nefgh");
+
+        let bounds = s.rotate_90(true);
+        assert_eq!(*bounds.dimension(), Dimension::from_wh(2, 4));
+        assert_eq!(symbols(&s.frames[0]), "eafbgchd");
+    }
+
+    #[test]
+    fn rotate_90_four_times_is_identity() {
+        let mut s = sprite("abcd\nefgh");
+        let before = positions(&s.frames[0]);
+
+        for _ in 0..4 {
+            s.rotate_90(true);
+        }
+        assert_eq!(positions(&s.frames[0]), before);
+
+        for _ in 0..4 {
+            s.rotate_90(false);
+        }
+        assert_eq!(positions(&s.frames[0]), before);
+    }
+
+    #[test]
+    fn rotate_90_empty_frame_is_noop() {
+        let mut s = Sprite::default();
+
+        assert_eq!(s.rotate_90(true), Bounds::empty());
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    fn move_frame_first_to_last() {
+        let mut s = sprite("a");
+        s.frames.insert(s.index + 1, Texels::new());
+        s.index += 1;
+        s.frames[1] = texels_from_str("b", Position2D::default());
+        s.frames.insert(s.index + 1, Texels::new());
+        s.index += 1;
+        s.frames[2] = texels_from_str("c", Position2D::default());
+
+        assert!(s.move_frame(0, 2));
+        assert_eq!(s.frame_index(), 2);
+        assert_eq!(symbols(&s.frames[0]), "b");
+        assert_eq!(symbols(&s.frames[1]), "c");
+        assert_eq!(symbols(&s.frames[2]), "a");
+    }
+
+    #[test]
+    fn move_frame_out_of_range() {
+        let mut s = sprite("a");
+        s.new_frame();
+
+        assert!(!s.move_frame(0, 2));
+        assert!(!s.move_frame(2, 0));
+        assert_eq!(s.frame_count(), 2);
+    }
+
+    #[test]
+    fn flip_twice_is_noop() {
+        let mut s = sprite("ab\nc");
+        let before = positions(&s.frames[0]);
+
+        s.flip_horizontal();
+        assert_ne!(positions(&s.frames[0]), before);
+        s.flip_horizontal();
+        assert_eq!(positions(&s.frames[0]), before);
+
+        s.flip_vertical();
+        s.flip_vertical();
+        assert_eq!(positions(&s.frames[0]), before);
+    }
+
+    #[test]
+    fn flip_symmetric_sprite_unchanged() {
+        let mut s = sprite("aba\nbcb\naba");
+        let before = positions(&s.frames[0]);
+
+        s.flip_horizontal();
+        assert_eq!(positions(&s.frames[0]), before);
+        s.flip_vertical();
+        assert_eq!(positions(&s.frames[0]), before);
+    }
+
+    #[test]
+    fn flip_preserves_dimension() {
+        let mut s = sprite("abc\nd");
+        s.new_frame();
+
+        assert_eq!(*s.flip_horizontal().dimension(), Dimension::from_wh(3, 2));
+        assert_eq!(*s.flip_vertical().dimension(), Dimension::from_wh(3, 2));
+        assert_eq!(
+            *s.flip_horizontal_all_frames().dimension(),
+            Dimension::from_wh(3, 2)
+        );
+        assert_eq!(
+            *s.flip_vertical_all_frames().dimension(),
+            Dimension::from_wh(3, 2)
+        );
+    }
+
+    #[test]
+    fn swap_frames_valid_indices() {
+        let mut s = sprite("a");
+        s.frames.insert(s.index + 1, Texels::new());
+        s.index += 1;
+        s.frames[1] = texels_from_str("b", Position2D::default());
+
+        assert!(s.swap_frames(0, 1));
+        assert_eq!(symbols(&s.frames[0]), "b");
+        assert_eq!(symbols(&s.frames[1]), "a");
+        assert_eq!(s.frame_index(), 1);
+    }
+
+    #[test]
+    fn swap_frames_equal_indices() {
+        let mut s = sprite("a");
+        s.new_frame();
+        let before = s.clone();
+
+        assert!(s.swap_frames(1, 1));
+        assert_eq!(s, before);
+    }
+
+    #[test]
+    fn swap_frames_out_of_range() {
+        let mut s = sprite("a");
+
+        assert!(!s.swap_frames(0, 1));
+        assert_eq!(symbols(&s.frames[0]), "a");
+    }
+
+    #[test]
+    fn scale_multiplies_texel_count() {
+        let mut s = sprite("abc\ndef\nghi\njkl");
+        s.new_frame();
+
+        let scaled = s.scale(2);
+        assert_eq!(scaled.all_iter().count(), s.all_iter().count() * 4);
+        assert_eq!(scaled.frame_count(), 2);
+        assert_eq!(Dimension::for_sprite(&scaled), Dimension::from_wh(6, 8));
+    }
+
+    #[test]
+    fn scale_preserves_colors() {
+        let mut s = sprite("ab");
+        s.frames[0][0].fg = 3;
+        s.frames[0][0].bg = 7;
+
+        let scaled = s.scale(3);
+        let block: Vec<&Texel> = scaled.frames[0].iter().filter(|t| t.pos.x < 3).collect();
+        assert_eq!(block.len(), 9);
+        assert!(block
+            .iter()
+            .all(|t| t.symbol == 'a' && t.fg == 3 && t.bg == 7));
+    }
+
+    #[test]
+    fn scale_factor_one_is_identical() {
+        let s = sprite("ab\nc");
+
+        assert_eq!(s.scale(1), s);
+        assert!(s.scale(0).is_empty());
+    }
+
+    #[test]
+    fn frame_valid_index() {
+        let mut s = sprite("a");
+        s.frames.insert(s.index + 1, Texels::new());
+        s.index += 1;
+
+        assert_eq!(s.frame(0).map(|f| symbols(f)), Some("a".to_string()));
+        assert_eq!(s.frame(1).map(|f| f.len()), Some(0));
+
+        let texel = s.frames[0][0].clone();
+        s.frame_mut(1).unwrap().push(texel);
+        assert_eq!(s.frame(1).map(|f| symbols(f)), Some("a".to_string()));
+    }
+
+    #[test]
+    fn frame_out_of_range() {
+        let mut s = sprite("a");
+
+        assert!(s.frame(1).is_none());
+        assert!(s.frame_mut(1).is_none());
+    }
+
+    #[test]
+    fn crop_keeps_only_area_texels() {
+        let s = sprite("abc\ndef\nghi");
+        let area = Bounds::Free(Position2D::from_xy(1, 1), Dimension::from_wh(2, 2));
+
+        let cropped = s.crop(area);
+        assert_eq!(
+            positions(&cropped.frames[0]),
+            vec![('e', 0, 0), ('f', 1, 0), ('h', 0, 1), ('i', 1, 1)]
+        );
+        assert_eq!(symbols(&s.frames[0]), "abcdefghi");
+    }
+
+    #[test]
+    fn crop_applies_to_every_frame() {
+        let mut s = sprite("abc\ndef");
+        s.frames.insert(s.index + 1, Texels::new());
+        s.index += 1;
+        s.frames[1] = texels_from_str("xyz", Position2D::from_xy(0, 1));
+        let area = Bounds::Free(Position2D::from_xy(2, 0), Dimension::from_wh(1, 2));
+
+        let cropped = s.crop(area);
+        assert_eq!(
+            positions(&cropped.frames[0]),
+            vec![('c', 0, 0), ('f', 0, 1)]
+        );
+        assert_eq!(positions(&cropped.frames[1]), vec![('z', 0, 1)]);
+    }
+
+    #[test]
+    fn flatten_transparent_reveals_bottom_frame() {
+        let mut s = sprite("ab");
+        s.frames.insert(s.index + 1, Texels::new());
+        s.index += 1;
+        s.frames[1] = texels_from_str(" c", Position2D::default());
+
+        let flat = s.flatten_transparent();
+        assert_eq!(flat.frame_count(), 1);
+        assert_eq!(symbols(&flat.frames[0]), "ac");
+    }
+
+    #[test]
+    fn flatten_transparent_keeps_colored_blanks() {
+        let mut s = sprite("ab");
+        s.frames.insert(s.index + 1, Texels::new());
+        s.index += 1;
+        s.frames[1] = texels_from_str(" ", Position2D::default());
+        s.frames[1][0].bg = 4;
+
+        let flat = s.flatten_transparent();
+        assert_eq!(symbols(&flat.frames[0]), " b");
+        assert_eq!(
+            flat.frames[0].iter().find(|t| t.pos.x == 0).map(|t| t.bg),
+            Some(4)
+        );
+    }
+
+    #[test]
+    fn frame_durations_set_and_get() {
+        let mut s = sprite("a");
+        s.new_frame();
+
+        assert_eq!(s.frame_duration(1), None);
+        assert!(s.set_frame_duration(1, 120));
+        assert_eq!(s.frame_duration(0), None);
+        assert_eq!(s.frame_duration(1), Some(120));
+        assert!(!s.set_frame_duration(2, 50));
+        assert_eq!(s.frame_duration(2), None);
+    }
+
+    #[test]
+    fn frame_durations_missing_entries_compare_as_zero() {
+        let mut s = sprite("a");
+        s.frames.push(Texels::new());
+        let mut other = s.clone();
+        other.durations = vec![0];
+
+        assert_eq!(s, other);
+        s.set_frame_duration(1, 40);
+        assert_ne!(s, other);
+        other.durations = vec![0, 40, 90];
+        assert_eq!(s, other);
+    }
+
+    #[test]
+    fn frame_durations_sized_in_constructors() {
+        let mut frames = vec![texels_from_str("a", Position2D::default()); 3];
+        frames[2] = Texels::new();
+
+        let s = Sprite::from(SpriteV1 { frames, index: 1 });
+        assert_eq!(s.durations, vec![0; 3]);
+        assert_eq!(s.frame_names, vec![None; 3]);
+        assert_eq!(Sprite::default().durations.len(), 1);
+        assert_eq!(sprite("ab").durations.len(), 1);
+    }
+
+    #[test]
+    fn sprite_v2_round_trip() {
+        let mut s = sprite("ab");
+        s.id = Some(7);
+        s.labels.insert("hero".to_owned(), String::new());
+        s.new_frame();
+
+        let back = Sprite::from(SpriteV2::from(s.clone()));
+        assert_eq!(back, s);
+
+        s.set_frame_duration(0, 80);
+        let lossy = Sprite::from(SpriteV2::from(s.clone()));
+        assert_eq!(lossy.frames, s.frames);
+        assert_eq!(lossy.id, Some(7));
+        assert_eq!(lossy.frame_duration(0), None);
+    }
+
+    #[cfg(feature = "serde_support")]
+    #[test]
+    fn frame_durations_serde_round_trip() {
+        let mut s = sprite("ab");
+        s.new_frame();
+        s.set_frame_duration(1, 250);
+        s.set_frame_name(0, Some("idle".to_string()));
+
+        let bytes = bincode::serialize(&s).unwrap();
+        let back: Sprite = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(back, s);
+        assert_eq!(back.frame_duration(1), Some(250));
+        assert_eq!(back.frame_name(0), Some("idle"));
+
+        let old = SpriteV2::from(s.clone());
+        let bytes = bincode::serialize(&old).unwrap();
+        let back: SpriteV2 = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(Sprite::from(back).durations, vec![0, 0]);
+    }
+
+    #[test]
+    fn concatenate_right_offsets_other() {
+        let left = sprite("ab\nc");
+        let right = sprite("xy");
+
+        let joined = left.concatenate_right(&right);
+        assert_eq!(joined.all_iter().count(), 5);
+        assert_eq!(
+            positions(&joined.frames[0]),
+            vec![
+                ('a', 0, 0),
+                ('b', 1, 0),
+                ('c', 0, 1),
+                ('x', 2, 0),
+                ('y', 3, 0)
+            ]
+        );
+        assert_eq!(left, sprite("ab\nc"));
+    }
+
+    #[test]
+    fn concatenate_below_offsets_other() {
+        let top = sprite("ab\nc");
+        let bottom = sprite("x");
+
+        let joined = top.concatenate_below(&bottom);
+        assert_eq!(joined.all_iter().count(), 4);
+        assert_eq!(
+            positions(&joined.frames[0]),
+            vec![('a', 0, 0), ('b', 1, 0), ('c', 0, 1), ('x', 0, 2)]
+        );
+    }
+
+    #[test]
+    fn concatenate_cycles_shorter_frames() {
+        let mut left = sprite("a");
+        left.frames.insert(left.index + 1, Texels::new());
+        left.index += 1;
+        left.frames[1] = texels_from_str("b", Position2D::default());
+        left.frames.insert(left.index + 1, Texels::new());
+        left.index += 1;
+        left.frames[2] = texels_from_str("c", Position2D::default());
+        let right = sprite("x");
+
+        let joined = left.concatenate_right(&right);
+        assert_eq!(joined.frame_count(), 3);
+        assert_eq!(joined.durations.len(), 3);
+        assert_eq!(symbols(&joined.frames[0]), "ax");
+        assert_eq!(symbols(&joined.frames[1]), "bx");
+        assert_eq!(symbols(&joined.frames[2]), "cx");
+    }
+
+    #[test]
+    fn add_label_rejects_duplicates() {
+        let mut s = Sprite::default();
+
+        assert!(s.add_label("enemy"));
+        assert!(!s.add_label("enemy"));
+        assert!(s.has_label("enemy"));
+        assert_eq!(s.labels.len(), 1);
+    }
+
+    #[test]
+    fn remove_label_missing() {
+        let mut s = Sprite::default();
+        s.add_label("enemy");
+
+        assert!(!s.remove_label("hero"));
+        assert!(s.remove_label("enemy"));
+        assert!(!s.remove_label("enemy"));
+        assert!(!s.has_label("enemy"));
+    }
+
+    #[test]
+    fn last_bounds_of_new_sprites() {
+        let s = sprite("ab\nc");
+
+        assert_eq!(*s.last_bounds().dimension(), Dimension::from_wh(2, 2));
+        assert_eq!(Sprite::default().last_bounds(), Bounds::empty());
+        assert_eq!(s.scale(1).last_bounds(), s.last_bounds());
+    }
+
+    #[test]
+    fn last_bounds_detects_growth() {
+        let mut s = sprite("ab");
+        let before = s.last_bounds();
+
+        let area = Bounds::Free(Position2D::from_xy(0, 1), Dimension::from_wh(3, 1));
+        let after = s.apply_symbol('x', 0, 1, area);
+        assert_eq!(s.last_bounds(), after);
+        assert!(after.dimension().w > before.dimension().w);
+        assert!(after.dimension().h > before.dimension().h);
+    }
+
+    #[test]
+    fn last_bounds_detects_shrinkage() {
+        let mut s = sprite("abc");
+        let before = s.last_bounds();
+
+        s.clear_symbol(Bounds::Free(
+            Position2D::from_xy(2, 0),
+            Dimension::from_wh(1, 1),
+        ));
+        assert!(s.last_bounds().dimension().w < before.dimension().w);
+    }
+
+    #[test]
+    fn last_bounds_after_mutators() {
+        let mut s = sprite("ab\nc");
+        let area = Bounds::Free(Position2D::from_xy(0, 2), Dimension::from_wh(2, 1));
+
+        s.apply_gradient(area, 1, 5, ColorMode::Fg, true);
+        assert_eq!(
+            s.last_bounds(),
+            Bounds::Free(Position2D::default(), Dimension::from_wh(2, 3))
+        );
+
+        s.flood_fill(Position2D::from_xy(1, 1), TexelBuilder::new('o').build());
+        assert_eq!(
+            s.texel_at(Position2D::from_xy(1, 1)).map(|t| t.symbol),
+            Some('o')
+        );
+        assert_eq!(
+            s.last_bounds(),
+            Bounds::Free(Position2D::default(), Dimension::from_wh(2, 3))
+        );
+
+        s.shift(Position2D::from_xy(1, 0), false);
+        assert_eq!(
+            s.last_bounds(),
+            Bounds::Free(Position2D::from_xy(1, 0), Dimension::from_wh(1, 3))
+        );
+
+        let mut texel = TexelBuilder::new('x').build();
+        texel.pos = Position2D::from_xy(3, 0);
+        s.set_texel(texel);
+        assert_eq!(
+            s.last_bounds(),
+            Bounds::Free(Position2D::from_xy(1, 0), Dimension::from_wh(3, 3))
+        );
+    }
+
+    #[cfg(feature = "serde_support")]
+    #[test]
+    fn last_bounds_after_deserialization() {
+        let s = sprite("ab\nc");
+
+        let bytes = bincode::serialize(&s).unwrap();
+        let back: Sprite = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(back.last_bounds(), s.last_bounds());
+    }
+
+    #[test]
+    fn overlay_zero_offset_reveals_below() {
+        let base = sprite("ab");
+        let top = sprite(" c");
+
+        let result = base.overlay(&top, Position2D::default());
+        assert_eq!(positions(&result.frames[0]), vec![('a', 0, 0), ('c', 1, 0)]);
+        assert_eq!(base, sprite("ab"));
+    }
+
+    #[test]
+    fn overlay_partially_outside() {
+        let base = sprite("ab");
+        let top = sprite("xy");
+
+        let result = base.overlay(&top, Position2D::from_xy(1, 1));
+        assert_eq!(
+            positions(&result.frames[0]),
+            vec![('a', 0, 0), ('b', 1, 0), ('x', 1, 1), ('y', 2, 1)]
+        );
+    }
+
+    #[test]
+    fn overlay_negative_offset() {
+        let base = sprite("ab");
+        let top = sprite("xy");
+
+        let result = base.overlay(&top, Position2D::from_xy(-1, 0));
+        assert_eq!(*result.last_bounds().position(), Position2D::default());
+        assert_eq!(
+            positions(&result.frames[0]),
+            vec![('b', 2, 0), ('x', 0, 0), ('y', 1, 0)]
+        );
+    }
+
+    #[test]
+    fn trim_blank_columns() {
+        let s = sprite(" a \n b ");
+
+        let trimmed = s.trim();
+        assert_eq!(
+            positions(&trimmed.frames[0]),
+            vec![('a', 0, 0), ('b', 0, 1)]
+        );
+    }
+
+    #[test]
+    fn trim_all_blank() {
+        let mut s = sprite("  \n  ");
+        s.new_frame();
+
+        let trimmed = s.trim();
+        assert_eq!(trimmed.frame_count(), 2);
+        assert!(trimmed.is_empty());
+    }
+
+    #[test]
+    fn trim_blank_top_row() {
+        let mut s = sprite("   \n ab");
+        s.frames.insert(s.index + 1, Texels::new());
+        s.index += 1;
+        s.frames[1] = texels_from_str("c  ", Position2D::default());
+
+        let trimmed = s.trim();
+        assert_eq!(
+            positions(&trimmed.frames[0]),
+            vec![('a', 0, 0), ('b', 1, 0)]
+        );
+        assert_eq!(positions(&trimmed.frames[1]), vec![('c', 0, 0)]);
+    }
+
+    #[test]
+    fn recolor_existing_adds_no_texels() {
+        let mut s = Sprite::from_texels(vec![
+            TexelBuilder::new('a').build(),
+            TexelBuilder::new('b')
+                .build()
+                .moved_from(Position2D::from_xy(-3, -2)),
+        ]);
+        let area = Bounds::Free(Position2D::default(), Dimension::from_wh(4, 3));
+
+        assert!(s.recolor_existing(ColorMode::Fg, 9, area));
+        assert_eq!(s.frames[0].len(), 2);
+        assert!(s.frames[0].iter().all(|t| t.fg == 9));
+    }
+
+    #[test]
+    fn recolor_existing_outside_area() {
+        let mut s = sprite("ab");
+        let area = Bounds::Free(Position2D::from_xy(0, 1), Dimension::from_wh(2, 2));
+
+        assert!(!s.recolor_existing(ColorMode::Bg, 9, area));
+        assert_eq!(s, sprite("ab"));
+    }
+
+    #[test]
+    fn pad_keeps_relative_positions() {
+        let s = sprite("ab\nc");
+
+        let padded = s.pad(1, 2, 0, 3, TexelBuilder::new('.').build());
+        let content: Vec<(char, i32, i32)> = positions(&padded.frames[0])
+            .into_iter()
+            .filter(|(c, _, _)| *c != '.')
+            .collect();
+        assert_eq!(content, vec![('a', 3, 1), ('b', 4, 1), ('c', 3, 2)]);
+    }
+
+    #[test]
+    fn pad_grows_dimension() {
+        let mut s = sprite("ab\ncd");
+        s.new_frame();
+
+        let padded = s.pad(1, 2, 3, 4, TexelBuilder::new('.').build());
+        assert_eq!(Dimension::for_sprite(&padded), Dimension::from_wh(8, 6));
+        assert_eq!(padded.frames[0].len(), 48);
+        assert_eq!(padded.frames[0], padded.frames[1]);
+    }
+
+    #[test]
+    fn pad_already_padded() {
+        let fill = TexelBuilder::new('.').build();
+        let s = sprite("a");
+
+        let twice = s.pad(1, 1, 1, 1, fill.clone()).pad(1, 1, 1, 1, fill);
+        assert_eq!(Dimension::for_sprite(&twice), Dimension::from_wh(5, 5));
+        assert_eq!(
+            positions(&twice.frames[0])
+                .iter()
+                .find(|(c, _, _)| *c == 'a'),
+            Some(&('a', 2, 2))
+        );
+    }
+
+    #[test]
+    fn flood_fill_enclosed_region() {
+        let mut s = sprite("#####\n#   #\n#   #\n#####");
+
+        s.flood_fill(Position2D::from_xy(2, 1), TexelBuilder::new('o').build());
+        assert_eq!(symbols(&s.frames[0]), "######ooo##ooo######");
+    }
+
+    #[test]
+    fn flood_fill_region_with_hole() {
+        let mut s = sprite("#####\n#   #\n# # #\n#   #\n#####");
+
+        s.flood_fill(Position2D::from_xy(1, 1), TexelBuilder::new('o').build());
+        assert_eq!(symbols(&s.frames[0]), "######ooo##o#o##ooo######");
+    }
+
+    #[test]
+    fn flood_fill_on_existing_texel() {
+        let mut s = sprite("aab\nbab");
+
+        s.flood_fill(Position2D::from_xy(0, 0), TexelBuilder::new('x').build());
+        assert_eq!(symbols(&s.frames[0]), "xxbbxb");
+        s.flood_fill(Position2D::from_xy(3, 0), TexelBuilder::new('y').build());
+        assert_eq!(symbols(&s.frames[0]), "xxbbxb");
+    }
+
+    #[test]
+    fn flood_fill_negative_coordinates() {
+        let mut s = Sprite::from_texels(texels_from_str(
+            "###\n# #\n###",
+            Position2D::from_xy(-3, -2),
+        ));
+
+        s.flood_fill(Position2D::from_xy(-2, -1), TexelBuilder::new('o').build());
+        assert_eq!(symbols(&s.frames[0]), "####o####");
+        assert_eq!(
+            s.texel_at(Position2D::from_xy(-2, -1)).map(|t| t.symbol),
+            Some('o')
+        );
+        s.flood_fill(Position2D::from_xy(0, 0), TexelBuilder::new('x').build());
+        assert_eq!(symbols(&s.frames[0]), "####o####");
+    }
+
+    #[test]
+    fn flood_fill_empty_cells_not_diagonal() {
+        let mut s = Sprite::from_texels(vec![
+            TexelBuilder::new('a').build(),
+            TexelBuilder::new('b')
+                .build()
+                .moved_from(Position2D::from_xy(-1, -1)),
+        ]);
+
+        s.flood_fill(Position2D::from_xy(1, 0), TexelBuilder::new('.').build());
+        assert_eq!(symbols(&s.frames[0]), "a.b");
+    }
+
+    #[test]
+    fn draw_line_includes_endpoints() {
+        let mut s = Sprite::default();
+
+        s.draw_line(
+            Position2D::from_xy(0, 0),
+            Position2D::from_xy(3, 2),
+            '*',
+            1,
+            0,
+        );
+        let drawn = positions(&s.frames[0]);
+        assert!(drawn.contains(&('*', 0, 0)));
+        assert!(drawn.contains(&('*', 3, 2)));
+        assert_eq!(drawn.len(), 4);
+    }
+
+    #[test]
+    fn draw_line_horizontal_and_vertical() {
+        let mut s = Sprite::default();
+
+        s.draw_line(
+            Position2D::from_xy(0, 0),
+            Position2D::from_xy(4, 0),
+            '-',
+            1,
+            0,
+        );
+        assert_eq!(s.frames[0].len(), 5);
+        s.draw_line(
+            Position2D::from_xy(0, 2),
+            Position2D::from_xy(0, 0),
+            '|',
+            1,
+            0,
+        );
+        assert_eq!(s.frames[0].len(), 7);
+        assert_eq!(symbols(&s.frames[0]), "|----||");
+    }
+
+    #[test]
+    fn draw_line_zero_length() {
+        let mut s = Sprite::default();
+        let at = Position2D::from_xy(1, 1);
+
+        s.draw_line(at, at, '*', 1, 0);
+        assert_eq!(s.frames[0].len(), 1);
+    }
+
+    #[test]
+    fn draw_rect_outline() {
+        let mut s = Sprite::default();
+
+        s.draw_rect(
+            Bounds::Free(Position2D::default(), Dimension::from_wh(4, 3)),
+            '#',
+            1,
+            0,
+        );
+        assert_eq!(s.frames[0].len(), 10);
+        assert_eq!(symbols(&s.frames[0]), "##########");
+    }
+
+    #[test]
+    fn is_animated_single_frame() {
+        assert!(!sprite("ab").is_animated());
+    }
+
+    #[test]
+    fn is_animated_identical_frames() {
+        let mut s = sprite("ab");
+        s.new_frame();
+        s.new_frame();
+
+        assert!(!s.is_animated());
+    }
+
+    #[test]
+    fn is_animated_differing_frames() {
+        let mut s = sprite("ab");
+        s.new_frame();
+        s.frames[1][0].symbol = 'x';
+
+        assert!(s.is_animated());
+    }
+
+    #[test]
+    fn apply_gradient_horizontal_endpoints() {
+        let mut s = sprite("abcd");
+        let area = Bounds::Free(Position2D::default(), Dimension::from_wh(4, 2));
+
+        assert!(s.apply_gradient(area, 10, 40, ColorMode::Fg, true));
+        assert_eq!(s.frames[0].len(), 8);
+        for t in s.frames[0].iter() {
+            let expected = [10, 20, 30, 40][t.pos.x as usize];
+            assert_eq!(t.fg, expected);
+        }
+    }
+
+    #[test]
+    fn apply_gradient_vertical_endpoints() {
+        let mut s = Sprite::default();
+        let area = Bounds::Free(Position2D::from_xy(1, 0), Dimension::from_wh(2, 3));
+
+        assert!(s.apply_gradient(area, 200, 100, ColorMode::Bg, false));
+        let rows: Vec<(i32, u8)> = s.frames[0].iter().map(|t| (t.pos.y, t.bg)).collect();
+        assert!(rows
+            .iter()
+            .filter(|(y, _)| *y == 0)
+            .all(|(_, bg)| *bg == 200));
+        assert!(rows
+            .iter()
+            .filter(|(y, _)| *y == 2)
+            .all(|(_, bg)| *bg == 100));
+        assert!(s.frames[0].iter().all(|t| t.symbol == ' '));
+    }
+
+    #[test]
+    fn apply_gradient_reports_changes_and_normalizes() {
+        let mut s = sprite("ab");
+        let area = Bounds::Free(Position2D::from_xy(-1, 0), Dimension::from_wh(3, 1));
+
+        assert!(s.apply_gradient(area, 10, 30, ColorMode::Fg, true));
+        assert_eq!(
+            positions(&s.frames[0]),
+            vec![(' ', 0, 0), ('a', 1, 0), ('b', 2, 0)]
+        );
+        assert_eq!(
+            s.texel_at(Position2D::from_xy(2, 0)).map(|t| t.fg),
+            Some(30)
+        );
+
+        let area = Bounds::Free(Position2D::default(), Dimension::from_wh(3, 1));
+        assert!(!s.apply_gradient(area, 10, 30, ColorMode::Fg, true));
+        assert!(s.apply_gradient(area, 10, 30, ColorMode::Bg, true));
+    }
+
+    #[test]
+    fn replace_symbol_missing_is_noop() {
+        let mut s = sprite("ab");
+
+        assert!(!s.replace_symbol('x', 'y'));
+        assert_eq!(s, sprite("ab"));
+        assert!(s.search('x').is_empty());
+    }
+
+    #[test]
+    fn replace_symbol_multiple_occurrences() {
+        let mut s = sprite("aba\nbab");
+        s.new_frame();
+
+        assert_eq!(
+            s.search('a'),
+            vec![
+                Position2D::from_xy(0, 0),
+                Position2D::from_xy(2, 0),
+                Position2D::from_xy(1, 1)
+            ]
+        );
+        assert!(s.replace_symbol('a', 'x'));
+        assert_eq!(symbols(&s.frames[1]), "xbxbxb");
+        assert_eq!(symbols(&s.frames[0]), "ababab");
+        assert!(s.replace_symbol_all_frames('b', 'y'));
+        assert_eq!(symbols(&s.frames[0]), "ayayay");
+        assert_eq!(symbols(&s.frames[1]), "xyxyxy");
+    }
+
+    #[test]
+    fn replace_symbol_same_character() {
+        let mut s = sprite("aba");
+
+        assert!(s.replace_symbol('a', 'a'));
+        assert_eq!(s, sprite("aba"));
+        assert_eq!(
+            s.search('a'),
+            vec![Position2D::from_xy(0, 0), Position2D::from_xy(2, 0)]
+        );
+    }
+
+    #[test]
+    fn count_symbols_empty_frame() {
+        assert!(Sprite::default().count_symbols().is_empty());
+    }
+
+    #[test]
+    fn count_symbols_only_spaces() {
+        let counts = sprite("   \n  ").count_symbols();
+
+        assert_eq!(counts.len(), 1);
+        assert_eq!(counts.get(&' '), Some(&5));
+    }
+
+    #[test]
+    fn count_symbols_multiple() {
+        let mut s = sprite("aab\nca");
+        s.new_frame();
+
+        let counts = s.count_symbols();
+        assert_eq!(counts.get(&'a'), Some(&3));
+        assert_eq!(counts.get(&'b'), Some(&1));
+        assert_eq!(counts.get(&'c'), Some(&1));
+        assert_eq!(s.count_symbols_all_frames().get(&'a'), Some(&6));
+    }
+
+    #[test]
+    fn new_frame_blank_after_current() {
+        let mut s = sprite("a");
+        s.new_frame();
+        s.apply_frame_change(Which::At(0));
+
+        s.new_frame_blank();
+        assert_eq!(s.frame_count(), 3);
+        assert_eq!(s.frame_index(), 1);
+        assert!(s.frames[1].is_empty());
+        assert_eq!(symbols(&s.frames[2]), "a");
+    }
+
+    #[test]
+    fn insert_frame_at_bounds_checked() {
+        let mut s = sprite("a");
+
+        assert_eq!(
+            s.insert_frame_at(1, texels_from_str("b", Position2D::default())),
+            Ok(())
+        );
+        assert_eq!(
+            s.insert_frame_at(0, texels_from_str("c", Position2D::default())),
+            Ok(())
+        );
+        assert_eq!(s.insert_frame_at(4, Texels::new()), Err(()));
+        assert_eq!(s.frame_count(), 3);
+        assert_eq!(symbols(&s.frames[0]), "c");
+        assert_eq!(symbols(&s.frames[2]), "b");
+        assert_eq!(s.frame_index(), 1);
+        assert_eq!(s.durations.len(), 3);
+    }
+
+    #[test]
+    fn clip_to_max_drops_excess() {
+        let mut s = sprite("abcd\nefgh\nijkl");
+
+        assert!(s.clip_to_max(Dimension::from_wh(2, 2)));
+        assert_eq!(symbols(&s.frames[0]), "abef");
+        assert!(!s.clip_to_max(Dimension::from_wh(2, 2)));
+    }
+
+    fn numbered(count: usize) -> Sprite {
+        let mut s = Sprite::from_texels(texels_from_str("0", Position2D::default()));
+        for i in 1..count {
+            let symbol = std::char::from_digit(i as u32, 10).unwrap();
+            s.new_frame_blank();
+            s.frames[i] = vec![TexelBuilder::new(symbol).build()];
+        }
+
+        s
+    }
+
+    fn frame_order(s: &Sprite) -> String {
+        s.frames.iter().map(|f| symbols(f)).collect()
+    }
+
+    #[test]
+    fn swap_frames_adjacent() {
+        let mut s = numbered(3);
+
+        assert!(s.swap_frames(1, 2));
+        assert_eq!(frame_order(&s), "021");
+        assert!(s.swap_frames(2, 2));
+        assert_eq!(frame_order(&s), "021");
+        assert!(!s.swap_frames(1, 3));
+    }
+
+    #[test]
+    fn move_frame_to_first_and_last() {
+        let mut s = numbered(4);
+
+        assert!(s.move_frame(2, 0));
+        assert_eq!(frame_order(&s), "2013");
+        assert_eq!(s.frame_index(), 0);
+        assert_eq!(
+            symbols(s.frame_iter().cloned().collect::<Texels>().as_slice()),
+            "2"
+        );
+
+        assert!(s.move_frame(1, 3));
+        assert_eq!(frame_order(&s), "2130");
+        assert_eq!(s.frame_index(), 3);
+    }
+
+    #[test]
+    fn draw_text_single_line() {
+        let mut s = sprite("....");
+
+        s.draw_text("hi", Position2D::from_xy(1, 0), 3, 4, SymbolStyles::new());
+        assert_eq!(symbols(&s.frames[0]), ".hi.");
+        assert_eq!(s.frames[0].len(), 4);
+        let h = s.frames[0].iter().find(|t| t.symbol == 'h').unwrap();
+        assert_eq!((h.fg, h.bg), (3, 4));
+    }
+
+    #[test]
+    fn draw_text_multi_line() {
+        let mut s = Sprite::default();
+
+        let bounds = s.draw_text("ab\ncde", Position2D::default(), 1, 0, SymbolStyles::new());
+        assert_eq!(*bounds.dimension(), Dimension::from_wh(3, 2));
+        assert_eq!(
+            positions(&s.frames[0]),
+            vec![
+                ('a', 0, 0),
+                ('b', 1, 0),
+                ('c', 0, 1),
+                ('d', 1, 1),
+                ('e', 2, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn reverse_frames_single_frame() {
+        let mut s = sprite("ab");
+
+        s.reverse_frames();
+        assert_eq!(s, sprite("ab"));
+    }
+
+    #[test]
+    fn reverse_frames_two_frames() {
+        let mut s = numbered(2);
+        s.set_frame_duration(0, 40);
+
+        s.reverse_frames();
+        assert_eq!(frame_order(&s), "10");
+        assert_eq!(s.frame_duration(1), Some(40));
+        assert_eq!(s.frame_index(), 0);
+    }
+
+    #[test]
+    fn reverse_frames_keeps_current_frame() {
+        let mut s = numbered(4);
+        s.apply_frame_change(Which::At(1));
+        let current: Texels = s.frame_iter().cloned().collect();
+
+        s.reverse_frames();
+        assert_eq!(s.frame_index(), 2);
+        assert_eq!(s.frame_iter().cloned().collect::<Texels>(), current);
+    }
+
+    #[test]
+    fn clone_frame_is_deep_copy() {
+        let mut s = numbered(2);
+        s.id = Some(3);
+        s.add_label("hero");
+        s.set_frame_duration(1, 90);
+
+        let mut clone = s.clone_frame(1).unwrap();
+        assert_eq!(clone.frame_count(), 1);
+        assert_eq!(clone.id, Some(3));
+        assert!(clone.has_label("hero"));
+        assert_eq!(clone.frame_duration(0), Some(90));
+
+        clone.frames[0][0].symbol = 'x';
+        assert_eq!(symbols(&s.frames[1]), "1");
+        assert!(s.clone_frame(2).is_none());
+    }
+
+    #[test]
+    fn clone_current_frame_uses_index() {
+        let mut s = numbered(3);
+        s.apply_frame_change(Which::At(2));
+
+        let clone = s.clone_current_frame();
+        assert_eq!(symbols(&clone.frames[0]), "2");
+    }
+
+    #[test]
+    fn try_frame_change_moved() {
+        let mut s = numbered(3);
+        s.apply_frame_change(Which::At(0));
+
+        assert!(s.try_frame_change(Which::At(2)));
+        assert!(s.try_frame_change(Which::Previous));
+        assert_eq!(s.frame_index(), 1);
+    }
+
+    #[test]
+    fn try_frame_change_noop() {
+        let mut s = numbered(2);
+        s.apply_frame_change(Which::At(1));
+
+        assert!(!s.try_frame_change(Which::Next));
+        assert!(!s.try_frame_change(Which::At(5)));
+        assert!(!s.try_frame_change(Which::All));
+        assert_eq!(s.frame_index(), 1);
+    }
+
+    #[test]
+    fn merge_frames_into_one_keeps_identity() {
+        let mut s = numbered(2);
+        s.id = Some(5);
+        s.add_label("ghost");
+        s.apply_frame_change(Which::At(1));
+
+        let merged = s.merge_frames_into_one();
+        assert_eq!(merged.frame_count(), 1);
+        assert_eq!(merged.frame_index(), 0);
+        assert_eq!(merged.id, Some(5));
+        assert!(merged.has_label("ghost"));
+        assert_eq!(symbols(&merged.frames[0]), "1");
+    }
+
+    #[test]
+    fn merge_frames_into_one_adds_new_positions() {
+        let mut s = sprite("a");
+        s.frames
+            .push(texels_from_str("b", Position2D::from_xy(1, 0)));
+
+        let merged = s.merge_frames_into_one();
+        assert_eq!(merged.frame_count(), 1);
+        assert_eq!(symbols(&merged.frames[0]), "ab");
+    }
+
+    #[test]
+    fn merge_frames_into_one_last_frame_wins() {
+        let mut s = sprite("ab");
+        s.frames.push(vec![Texel {
+            pos: Position2D::from_xy(1, 0),
+            ..Texel::default()
+        }]);
+
+        let merged = s.merge_frames_into_one();
+        assert_eq!(symbols(&merged.frames[0]), "a ");
+        assert_eq!(symbols(&s.flatten_transparent().frames[0]), "ab");
+    }
+
+    #[test]
+    fn merge_frames_into_one_single_frame_identity() {
+        let s = sprite("ab\ncd");
+
+        assert_eq!(s.merge_frames_into_one(), s);
+    }
+
+    #[test]
+    fn frame_diff_identical() {
+        let mut s = sprite("ab");
+        s.new_frame();
+
+        assert_eq!(s.frame_diff(0, 1), Some(Texels::new()));
+        assert_eq!(s.frame_diff(0, 2), None);
+    }
+
+    #[test]
+    fn frame_diff_completely_different() {
+        let mut s = sprite("ab");
+        s.new_frame_blank();
+        s.frames[1] = texels_from_str("xy", Position2D::from_xy(0, 1));
+
+        let diff = s.frame_diff(0, 1).unwrap();
+        assert_eq!(
+            positions(&diff),
+            vec![(' ', 0, 0), (' ', 1, 0), ('x', 0, 1), ('y', 1, 1)]
+        );
+        assert!(diff
+            .iter()
+            .filter(|t| t.symbol == ' ')
+            .all(|t| t.is_transparent()));
+    }
+
+    #[test]
+    fn frame_diff_partial_overlap() {
+        let mut s = sprite("abc");
+        s.new_frame_blank();
+        s.frames[1] = texels_from_str("axcd", Position2D::default());
+        s.frames[1][2].fg = 7;
+
+        let diff = s.frame_diff(0, 1).unwrap();
+        assert_eq!(
+            positions(&diff),
+            vec![('c', 2, 0), ('d', 3, 0), ('x', 1, 0)]
+        );
+    }
+
+    #[test]
+    fn apply_to_all_frames_keeps_index() {
+        let mut s = numbered(3);
+        s.apply_frame_change(Which::At(1));
+        let area = Bounds::Free(Position2D::default(), Dimension::unit());
+
+        assert!(s.apply_color_to_all_frames(ColorMode::Fg, 9, area));
+        assert!(s.apply_style_to_all_frames(SymbolStyle::Italic, area));
+        assert_eq!(s.frame_index(), 1);
+        assert!(s
+            .all_iter()
+            .all(|t| t.fg == 9 && t.styles.contains(SymbolStyle::Italic)));
+    }
+
+    #[test]
+    fn apply_symbol_to_all_frames_every_frame() {
+        let mut s = numbered(2);
+        let area = Bounds::Free(Position2D::default(), Dimension::from_wh(2, 1));
+
+        assert!(s.apply_symbol_to_all_frames('#', 1, 2, area));
+        assert_eq!(frame_order(&s), "####");
+        assert_eq!(s.frame_index(), 1);
+        assert!(!s.apply_symbol_to_all_frames('#', 1, 2, Bounds::empty()));
+        assert!(!s.apply_symbol_to_all_frames('#', 1, 2, area));
+    }
+
+    #[test]
+    fn apply_to_all_frames_negative_origin() {
+        let mut s = sprite("a");
+        s.frames.push(texels_from_str("b", Position2D::default()));
+        let area = Bounds::Free(Position2D::from_xy(-1, 0), Dimension::unit());
+
+        assert!(s.apply_symbol_to_all_frames('#', 1, 2, area));
+        assert_eq!(frame_order(&s), "#a#b");
+        assert_eq!(
+            s.texel_at(Position2D::from_xy(0, 0)).map(|t| t.symbol),
+            Some('#')
+        );
+
+        let mut s = sprite("a");
+        s.frames.push(texels_from_str("b", Position2D::default()));
+
+        assert!(s.apply_color_to_all_frames(ColorMode::Fg, 9, area));
+        assert_eq!(frame_order(&s), " a b");
+        assert_eq!(s.all_iter().filter(|t| t.fg == 9).count(), 2);
+        assert_eq!(
+            s.all_iter()
+                .filter(|t| t.pos == Position2D::default())
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn ensure_frame_extends() {
+        let mut s = sprite("a");
+
+        s.ensure_frame(4);
+        assert_eq!(s.frame_count(), 5);
+        assert_eq!(s.durations.len(), 5);
+        assert!(s.frames[1..].iter().all(|f| f.is_empty()));
+        assert_eq!(s.frame_index(), 0);
+
+        s.ensure_frame(2);
+        assert_eq!(s.frame_count(), 5);
+    }
+
+    #[test]
+    fn frame_bounds_empty_and_out_of_range() {
+        let mut s = sprite("a");
+        s.new_frame_blank();
+
+        assert_eq!(s.frame_bounds(1), Some(Bounds::empty()));
+        assert_eq!(s.frame_bounds(2), None);
+    }
+
+    #[test]
+    fn frame_bounds_single_texel() {
+        let s = Sprite::from_texels(vec![TexelBuilder::new('a')
+            .at(Position2D::from_xy(3, 2))
+            .build()]);
+
+        assert_eq!(
+            s.frame_bounds(0),
+            Some(Bounds::Free(Position2D::from_xy(3, 2), Dimension::unit()))
+        );
+    }
+
+    #[test]
+    fn frame_bounds_multiple_rows() {
+        let mut s = sprite("a");
+        s.new_frame_blank();
+        s.frames[1] = texels_from_str("ab\nc\nd", Position2D::from_xy(-1, 4));
+
+        assert_eq!(
+            s.frame_bounds(1),
+            Some(Bounds::Free(
+                Position2D::from_xy(-1, 4),
+                Dimension::from_wh(2, 3)
+            ))
+        );
+        assert_eq!(s.frames[1][0].pos, Position2D::from_xy(-1, 4));
+    }
+
+    #[test]
+    fn symbol_and_color_at() {
+        let mut s = sprite("ab");
+        s.frames[0][1].fg = 6;
+        s.frames[0][1].bg = 2;
+        let pos = Position2D::from_xy(1, 0);
+
+        assert_eq!(s.symbol_at(pos), Some('b'));
+        assert_eq!(s.color_at(pos, ColorMode::Fg), Some(6));
+        assert_eq!(s.color_at(pos, ColorMode::Bg), Some(2));
+    }
+
+    #[test]
+    fn symbol_and_color_at_absent() {
+        let s = sprite("ab");
+        let pos = Position2D::from_xy(0, 1);
+
+        assert_eq!(s.symbol_at(pos), None);
+        assert_eq!(s.color_at(pos, ColorMode::Fg), None);
+    }
+
+    #[test]
+    fn max_frame_dimension_single_frame() {
+        assert_eq!(
+            sprite("abc\nd").max_frame_dimension(),
+            Dimension::from_wh(3, 2)
+        );
+    }
+
+    #[test]
+    fn max_frame_dimension_wide_and_tall_frames() {
+        let mut s = sprite("abcd");
+        s.new_frame_blank();
+        s.frames[1] = texels_from_str("a\nb\nc", Position2D::default());
+
+        assert_eq!(s.max_frame_dimension(), Dimension::from_wh(4, 3));
+    }
+
+    #[test]
+    fn max_frame_dimension_empty_frames() {
+        let mut s = Sprite::default();
+        s.new_frame_blank();
+
+        assert_eq!(s.max_frame_dimension(), Dimension::default());
+    }
+
+    #[test]
+    fn normalize_frame_dimensions_pads_frames() {
+        let mut s = sprite("abc");
+        s.new_frame_blank();
+        s.frames[1] = texels_from_str("x\ny", Position2D::default());
+
+        assert!(s.normalize_frame_dimensions());
+        assert_eq!(s.frame_bounds(0), s.frame_bounds(1));
+        assert_eq!(s.frames[0].len(), 6);
+        assert_eq!(s.frames[1].len(), 6);
+        assert_eq!(symbols(&s.frames[0]), "abc   ");
+        assert_eq!(symbols(&s.frames[1]), "x  y  ");
+        assert!(!s.normalize_frame_dimensions());
+    }
+
+    #[test]
+    fn trim_in_place_away_from_origin() {
+        let mut s = Sprite::from_texels(texels_from_str("ab", Position2D::from_xy(3, 2)));
+
+        let bounds = s.trim_in_place();
+        assert_eq!(
+            bounds,
+            Bounds::Free(Position2D::from_xy(3, 2), Dimension::from_wh(2, 1))
+        );
+        assert_eq!(positions(&s.frames[0]), vec![('a', 0, 0), ('b', 1, 0)]);
+    }
+
+    #[test]
+    fn trim_in_place_padded_area_keeps_frames_aligned() {
+        let mut s = sprite("    \n a  \n    ");
+        s.new_frame_blank();
+        s.frames[1] = texels_from_str("    \n    \n  b ", Position2D::default());
+
+        let bounds = s.trim_in_place();
+        assert_eq!(*bounds.dimension(), Dimension::from_wh(2, 2));
+        assert_eq!(symbols(&s.frames[0]), "a   ");
+        assert_eq!(symbols(&s.frames[1]), "   b");
+        assert_eq!(s.frame_bounds(0), s.frame_bounds(1));
+    }
+
+    #[test]
+    fn frame_hash_identical_frames() {
+        let mut s = sprite("ab\nc");
+        s.new_frame();
+        s.frames[1].reverse();
+
+        assert!(s.frame_hash(0).is_some());
+        assert_eq!(s.frame_hash(0), s.frame_hash(1));
+        assert_eq!(s.frame_hash(2), None);
+    }
+
+    #[test]
+    fn frame_hash_one_texel_difference() {
+        let mut s = sprite("ab\nc");
+        s.new_frame();
+        s.frames[1][2].fg = 1;
+
+        assert_ne!(s.frame_hash(0), s.frame_hash(1));
+    }
+
+    #[test]
+    fn frame_hash_empty_frames() {
+        let mut s = Sprite::default();
+        s.new_frame_blank();
+
+        assert_eq!(s.frame_hash(0), s.frame_hash(1));
+        assert_eq!(s.frame_hash(0), Sprite::default().frame_hash(0));
+    }
+
+    #[test]
+    fn dedup_positions_keeps_last() {
+        let mut s = sprite("ab");
+        s.frames[0].push(TexelBuilder::new('x').build());
+
+        s.dedup_positions();
+        assert_eq!(positions(&s.frames[0]), vec![('b', 1, 0), ('x', 0, 0)]);
+        assert_eq!(s.frames[0][0].symbol, 'b');
+    }
+
+    #[test]
+    fn deduplicate_frames_none() {
+        let mut s = numbered(3);
+
+        assert_eq!(s.deduplicate_frames(), 0);
+        assert_eq!(s.frame_count(), 3);
+    }
+
+    #[test]
+    fn deduplicate_frames_all() {
+        let mut s = sprite("a");
+        s.new_frame();
+        s.new_frame();
+
+        assert_eq!(s.deduplicate_frames(), 2);
+        assert_eq!(s.frame_count(), 1);
+        assert_eq!(s.frame_index(), 0);
+        assert_eq!(s.durations.len(), 1);
+    }
+
+    #[test]
+    fn deduplicate_frames_ignores_texel_order() {
+        let mut s = sprite("ab");
+        let mut reversed = s.frames[0].clone();
+        reversed.reverse();
+        s.frames.push(reversed);
+        s.frames.push(texels_from_str("ba", Position2D::default()));
+
+        assert_eq!(s.deduplicate_frames(), 1);
+        assert_eq!(frame_order(&s), "abba");
+    }
+
+    #[test]
+    fn deduplicate_frames_only_consecutive() {
+        let mut s = numbered(2);
+        s.new_frame();
+        s.apply_frame_change(Which::At(0));
+        s.new_frame();
+        assert_eq!(frame_order(&s), "0011");
+
+        assert_eq!(s.deduplicate_frames(), 2);
+        assert_eq!(frame_order(&s), "01");
+    }
+
+    #[test]
+    fn deduplicate_frames_single() {
+        let mut s = sprite("a");
+
+        assert_eq!(s.deduplicate_frames(), 0);
+        assert_eq!(s, sprite("a"));
+    }
+
+    fn text_of(texels: &[Texel]) -> String {
+        texels.iter().map(|t| t.symbol).collect()
+    }
+
+    #[test]
+    fn sorted_texels_independent_of_insertion_order() {
+        let a = sprite("ab\ncd");
+        let mut b = a.clone();
+        b.frames[0].reverse();
+        assert_ne!(a, b);
+
+        assert_eq!(a.sorted_texels(Which::At(0)), b.sorted_texels(Which::At(0)));
+        assert_eq!(symbols(&b.sorted_texels(Which::All)), "abcd");
+        assert_eq!(b.sorted_texels(Which::At(0))[0].symbol, 'a');
+    }
+
+    #[test]
+    fn sorted_texels_frame_selection() {
+        let mut s = numbered(3);
+        s.apply_frame_change(Which::At(1));
+
+        assert_eq!(text_of(&s.sorted_texels(Which::All)), "012");
+        assert_eq!(text_of(&s.sorted_texels(Which::Next)), "2");
+        assert_eq!(text_of(&s.sorted_texels(Which::Previous)), "0");
+        assert!(s.sorted_texels(Which::At(3)).is_empty());
+    }
+
+    #[test]
+    fn labels_case_sensitive() {
+        let mut s = Sprite::default();
+
+        assert!(s.add_label("Wall"));
+        assert!(s.add_label("wall"));
+        assert!(s.has_label("Wall"));
+        assert!(!s.has_label("WALL"));
+        assert!(s.remove_label("wall"));
+        assert!(s.has_label("Wall"));
+    }
+
+    #[test]
+    fn resize_grows_with_fill() {
+        let mut s = sprite("ab");
+
+        s.resize(
+            Dimension::from_wh(3, 2),
+            Some(TexelBuilder::new('.').fg(2).build()),
+        );
+        assert_eq!(symbols(&s.frames[0]), "ab....");
+        assert_eq!(s.max_frame_dimension(), Dimension::from_wh(3, 2));
+        assert!(s.frames[0]
+            .iter()
+            .filter(|t| t.symbol == '.')
+            .all(|t| t.fg == 2));
+    }
+
+    #[test]
+    fn resize_shrinks_without_fill() {
+        let mut s = sprite("abc\ndef");
+
+        s.resize(Dimension::from_wh(2, 3), None);
+        assert_eq!(symbols(&s.frames[0]), "abde");
+        assert_eq!(s.max_frame_dimension(), Dimension::from_wh(2, 2));
+    }
+
+    #[test]
+    fn ensure_id_calls_generator_once() {
+        let mut s = Sprite::default();
+        let mut calls = 0;
+        let mut generator = || {
+            calls += 1;
+            42
+        };
+
+        assert_eq!(s.ensure_id(&mut generator), 42);
+        assert_eq!(s.ensure_id(&mut generator), 42);
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn ensure_id_keeps_existing() {
+        let mut s = Sprite::default();
+        s.set_id(7);
+
+        assert_eq!(s.ensure_id(&mut || panic!("generator called")), 7);
+        assert_eq!(s.id, Some(7));
+    }
+
+    #[test]
+    fn apply_gradient_towards_horizontal() {
+        let mut s = Sprite::default();
+        let area = Bounds::Free(Position2D::default(), Dimension::from_wh(3, 1));
+
+        assert!(s.apply_gradient_towards(ColorMode::Fg, 10, 30, Direction::Left, area));
+        let colors: Vec<(i32, u8)> = s
+            .sorted_texels(Which::At(0))
+            .iter()
+            .map(|t| (t.pos.x, t.fg))
+            .collect();
+        assert_eq!(colors, vec![(0, 30), (1, 20), (2, 10)]);
+
+        assert!(s.apply_gradient_towards(ColorMode::Fg, 10, 30, Direction::Right, area));
+        let colors: Vec<u8> = s.sorted_texels(Which::At(0)).iter().map(|t| t.fg).collect();
+        assert_eq!(colors, vec![10, 20, 30]);
+    }
+
+    #[test]
+    fn apply_gradient_towards_vertical() {
+        let mut s = Sprite::default();
+        let area = Bounds::Free(Position2D::default(), Dimension::from_wh(1, 4));
+
+        assert!(s.apply_gradient_towards(ColorMode::Bg, 0, 255, Direction::Top, area));
+        let colors: Vec<u8> = s.sorted_texels(Which::At(0)).iter().map(|t| t.bg).collect();
+        assert_eq!(colors.first(), Some(&255));
+        assert_eq!(colors.last(), Some(&0));
+
+        assert!(s.apply_gradient_towards(ColorMode::Bg, 0, 255, Direction::Bottom, area));
+        let colors: Vec<u8> = s.sorted_texels(Which::At(0)).iter().map(|t| t.bg).collect();
+        assert_eq!(colors.first(), Some(&0));
+        assert_eq!(colors.last(), Some(&255));
+    }
+
+    #[test]
+    fn shift_without_wrap_drops_edge() {
+        let mut s = sprite("abc\ndef");
+
+        s.shift(Position2D::from_xy(1, 0), false);
+        assert_eq!(
+            positions(&s.frames[0]),
+            vec![('a', 1, 0), ('b', 2, 0), ('d', 1, 1), ('e', 2, 1)]
+        );
+    }
+
+    #[test]
+    fn shift_with_wrap_cycles() {
+        let mut s = sprite("abc\ndef");
+
+        s.shift(Position2D::from_xy(-1, 1), true);
+        assert_eq!(s.frames[0].len(), 6);
+        assert_eq!(symbols(&s.frames[0]), "efdbca");
+
+        s.shift(Position2D::from_xy(1, -1), true);
+        assert_eq!(symbols(&s.frames[0]), "abcdef");
+    }
+}
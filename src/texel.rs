@@ -1,4 +1,7 @@
-use crate::{Position2D, SymbolStyles, DEFAULT_BG_U8, DEFAULT_FG_U8};
+use crate::{
+    rgb_from_ansi256, Bounds, Position2D, Rgb, SymbolStyle, SymbolStyles, DEFAULT_BG_U8,
+    DEFAULT_FG_U8,
+};
 
 #[cfg(feature = "serde_support")]
 use serde::{Deserialize, Serialize};
@@ -6,7 +9,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// Base building block, "textual pixel" consisting of coordinates, symbol, styles and colors
 ///
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 #[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
 pub struct Texel {
     pub pos: Position2D,
@@ -22,6 +25,19 @@ pub struct Texel {
 /// Vector of Texels
 pub type Texels = Vec<Texel>;
 
+impl Default for Texel {
+    /// Blank "background" texel at origin with default colors
+    fn default() -> Self {
+        Texel {
+            pos: Position2D::default(),
+            symbol: ' ',
+            styles: SymbolStyles::new(),
+            fg: DEFAULT_FG_U8,
+            bg: DEFAULT_BG_U8,
+        }
+    }
+}
+
 impl Texel {
     /// Clones this texel moved to a new position
     pub fn moved_from(&self, pos: Position2D) -> Self {
@@ -31,28 +47,122 @@ impl Texel {
 
         result
     }
+
+    /// Foreground color as `Rgb`, see `rgb_from_ansi256`
+    pub fn fg_rgb(&self) -> Rgb {
+        rgb_from_ansi256(self.fg)
+    }
+
+    /// Background color as `Rgb`, see `rgb_from_ansi256`
+    pub fn bg_rgb(&self) -> Rgb {
+        rgb_from_ansi256(self.bg)
+    }
+
+    /// Compares symbol, styles and colors ignoring position
+    pub fn same_appearance(&self, other: &Texel) -> bool {
+        self.symbol == other.symbol
+            && self.styles == other.styles
+            && self.fg == other.fg
+            && self.bg == other.bg
+    }
+
+    /// Transparent texels are blank symbols with default background color
+    pub fn is_transparent(&self) -> bool {
+        self.symbol == ' ' && self.bg == DEFAULT_BG_U8
+    }
+
+    /// Layers *above* texel over this one, transparent *above* keeps this texel visible
+    pub fn overlay(&self, above: &Texel) -> Texel {
+        if above.is_transparent() {
+            self.clone()
+        } else {
+            above.clone()
+        }
+    }
+}
+
+///
+/// Fluent `Texel` builder, unset fields use `Texel::default()` values
+///
+#[derive(Debug, Clone)]
+pub struct TexelBuilder {
+    texel: Texel,
+}
+
+impl TexelBuilder {
+    /// Starts building a texel with given *symbol*
+    pub fn new(symbol: char) -> Self {
+        TexelBuilder {
+            texel: Texel {
+                symbol,
+                ..Texel::default()
+            },
+        }
+    }
+
+    /// Sets position
+    pub fn at(mut self, pos: Position2D) -> Self {
+        self.texel.pos = pos;
+        self
+    }
+
+    /// Sets foreground color
+    pub fn fg(mut self, fg: u8) -> Self {
+        self.texel.fg = fg;
+        self
+    }
+
+    /// Sets background color
+    pub fn bg(mut self, bg: u8) -> Self {
+        self.texel.bg = bg;
+        self
+    }
+
+    /// Adds *style*, multiple calls combine
+    pub fn style(mut self, style: SymbolStyle) -> Self {
+        self.texel.styles.insert(style);
+        self
+    }
+
+    /// Finishes building the `Texel`
+    pub fn build(self) -> Texel {
+        self.texel
+    }
 }
 
-/// Create a Texels vector from &str
+/// Create a Texels vector from &str, newlines continue at *start* x on the next row
 pub fn texels_from_str(s: &str, start: Position2D) -> Texels {
     let mut result = Vec::with_capacity(s.len());
+    let mut pos = start;
+
+    for c in s.chars() {
+        if c == '\n' {
+            pos = Position2D::from_xy(start.x, pos.y + 1);
+            continue;
+        }
 
-    for (i, c) in s.chars().enumerate() {
         result.push(Texel {
             symbol: c,
-            pos: Position2D {
-                x: start.x + i as i32,
-                y: start.y,
-            },
+            pos,
             styles: SymbolStyles::new(),
             bg: DEFAULT_BG_U8,
             fg: DEFAULT_FG_U8,
         });
+        pos.x += 1;
     }
 
     result
 }
 
+/// Copies texels inside given `Bounds` *area* moved relative to the area position
+pub fn texels_in_bounds(texels: &Texels, area: Bounds) -> Texels {
+    texels
+        .iter()
+        .filter(|t| area.contains(t.pos))
+        .map(|t| t.moved_from(*area.position()))
+        .collect()
+}
+
 ///
 /// Writes given &str to Texels list starting at given position
 /// *NOTE* - does not expand the list, if EOL would be reached false it returned
@@ -68,3 +178,244 @@ pub fn write_to_texels(s: &str, texels: &mut Texels, start_x: usize) -> bool {
 
     true
 }
+
+///
+/// Writes given &str to Texels list starting at given position
+/// expanding the list with default texels at *row_y* row as needed
+///
+pub fn write_to_texels_expanding(s: &str, texels: &mut Texels, start_x: usize, row_y: i32) {
+    for (i, c) in s.chars().enumerate() {
+        let index = start_x + i;
+
+        while texels.len() <= index {
+            let x = texels.len() as i32;
+            texels.push(Texel {
+                pos: Position2D::from_xy(x, row_y),
+                ..Texel::default()
+            });
+        }
+
+        texels[index].symbol = c;
+    }
+}
+
+///
+/// Writes given &str to Texels list starting at given position, wrapping to
+/// *start_x* column of the next row when end of *row_width* wide row is reached
+/// *NOTE* - does not expand the list, returns number of characters written
+///
+pub fn write_to_texels_wrapping(
+    s: &str,
+    texels: &mut Texels,
+    start_x: usize,
+    row_width: usize,
+) -> usize {
+    if row_width == 0 {
+        return 0;
+    }
+
+    let column = start_x % row_width;
+    let mut index = start_x;
+    let mut written = 0;
+
+    for c in s.chars() {
+        if index >= texels.len() {
+            break; // no expansion
+        }
+
+        texels[index].symbol = c;
+        written += 1;
+        index += 1;
+
+        if index % row_width == 0 {
+            index += column; // next row at start column
+        }
+    }
+
+    written
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{texels_from_str, Dimension};
+
+    fn blank_texels(count: usize) -> Texels {
+        texels_from_str(&" ".repeat(count), Position2D::default())
+    }
+
+    fn text(texels: &[Texel]) -> String {
+        texels.iter().map(|t| t.symbol).collect()
+    }
+
+    #[test]
+    fn write_to_texels_wrapping_continues_on_next_row() {
+        let mut texels = blank_texels(6);
+
+        assert_eq!(write_to_texels_wrapping("abcd", &mut texels, 1, 3), 4);
+        assert_eq!(text(&texels), " ab cd");
+    }
+
+    #[test]
+    fn write_to_texels_wrapping_stops_when_full() {
+        let mut texels = blank_texels(6);
+
+        assert_eq!(write_to_texels_wrapping("abcdefgh", &mut texels, 0, 3), 6);
+        assert_eq!(text(&texels), "abcdef");
+        assert_eq!(write_to_texels_wrapping("x", &mut texels, 0, 0), 0);
+    }
+
+    #[test]
+    fn texels_in_bounds_filters_and_reorigins() {
+        let texels = texels_from_str("abc\ndef", Position2D::from_xy(1, 1));
+        let area = Bounds::Free(Position2D::from_xy(2, 1), Dimension::from_wh(2, 1));
+
+        let inside = texels_in_bounds(&texels, area);
+        assert_eq!(text(&inside), "bc");
+        assert_eq!(inside[0].pos, Position2D::from_xy(0, 0));
+        assert_eq!(inside[1].pos, Position2D::from_xy(1, 0));
+    }
+
+    #[test]
+    fn texels_in_bounds_outside_area() {
+        let texels = texels_from_str("abc", Position2D::default());
+        let area = Bounds::Free(Position2D::from_xy(0, 1), Dimension::from_wh(3, 3));
+
+        assert!(texels_in_bounds(&texels, area).is_empty());
+    }
+
+    #[test]
+    fn texel_rgb_colors() {
+        let texel = TexelBuilder::new('x').fg(231).bg(16).build();
+
+        assert_eq!(texel.fg_rgb(), Rgb::from((255, 255, 255)));
+        assert_eq!(texel.bg_rgb(), Rgb::from((0, 0, 0)));
+    }
+
+    #[test]
+    fn default_texel_fields() {
+        let texel = Texel::default();
+
+        assert_eq!(texel.pos, Position2D::default());
+        assert_eq!(texel.symbol, ' ');
+        assert!(texel.styles.is_empty());
+        assert_eq!(texel.fg, DEFAULT_FG_U8);
+        assert_eq!(texel.bg, DEFAULT_BG_U8);
+        assert!(texel.is_transparent());
+    }
+
+    #[test]
+    fn builder_sets_every_field() {
+        let texel = TexelBuilder::new('x')
+            .at(Position2D::from_xy(2, 3))
+            .fg(4)
+            .bg(5)
+            .style(SymbolStyle::Bold)
+            .style(SymbolStyle::Underline)
+            .build();
+
+        assert_eq!(texel.symbol, 'x');
+        assert_eq!(texel.pos, Position2D::from_xy(2, 3));
+        assert_eq!(texel.fg, 4);
+        assert_eq!(texel.bg, 5);
+        assert_eq!(texel.styles.len(), 2);
+        assert!(texel.styles.contains(SymbolStyle::Bold));
+        assert!(texel.styles.contains(SymbolStyle::Underline));
+        assert!(!texel.styles.contains(SymbolStyle::Italic));
+    }
+
+    #[test]
+    fn builder_defaults() {
+        assert_eq!(TexelBuilder::new(' ').build(), Texel::default());
+    }
+
+    #[test]
+    fn texels_from_str_two_lines() {
+        let texels = texels_from_str("ab\ncde", Position2D::from_xy(2, 5));
+        let cells: Vec<(char, i32, i32)> = texels
+            .iter()
+            .map(|t| (t.symbol, t.pos.x, t.pos.y))
+            .collect();
+
+        assert_eq!(
+            cells,
+            vec![
+                ('a', 2, 5),
+                ('b', 3, 5),
+                ('c', 2, 6),
+                ('d', 3, 6),
+                ('e', 4, 6)
+            ]
+        );
+    }
+
+    #[test]
+    fn texels_from_str_single_line() {
+        let texels = texels_from_str("abc", Position2D::from_xy(-1, 0));
+
+        assert_eq!(text(&texels), "abc");
+        assert!(texels.iter().all(|t| t.pos.y == 0));
+        assert_eq!(texels[2].pos.x, 1);
+    }
+
+    #[test]
+    fn write_to_texels_expanding_empty() {
+        let mut texels = Texels::new();
+
+        write_to_texels_expanding("hi", &mut texels, 1, 3);
+        assert_eq!(text(&texels), " hi");
+        assert!(texels.iter().all(|t| t.pos.y == 3));
+        assert_eq!(texels[2].pos.x, 2);
+    }
+
+    #[test]
+    fn write_to_texels_expanding_partial() {
+        let mut texels = texels_from_str("abc", Position2D::default());
+
+        write_to_texels_expanding("xyz", &mut texels, 2, 0);
+        assert_eq!(text(&texels), "abxyz");
+        assert_eq!(texels[4].pos, Position2D::from_xy(4, 0));
+        assert!(!write_to_texels("toolong", &mut texels, 0));
+    }
+
+    #[test]
+    fn overlay_transparent_keeps_below() {
+        let below = TexelBuilder::new('a').fg(3).build();
+        let above = Texel::default();
+
+        assert!(above.is_transparent());
+        assert_eq!(below.overlay(&above), below);
+    }
+
+    #[test]
+    fn overlay_opaque_replaces_below() {
+        let below = TexelBuilder::new('a').build();
+        let colored_blank = TexelBuilder::new(' ').bg(4).build();
+
+        assert!(!colored_blank.is_transparent());
+        assert_eq!(below.overlay(&colored_blank), colored_blank);
+        let symbol = TexelBuilder::new('b').build();
+        assert_eq!(below.overlay(&symbol), symbol);
+    }
+
+    #[test]
+    fn same_appearance_ignores_position() {
+        let a = TexelBuilder::new('x')
+            .fg(2)
+            .style(SymbolStyle::Bold)
+            .build();
+        let b = a.moved_from(Position2D::from_xy(-4, 1));
+
+        assert!(a.same_appearance(&b));
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn same_appearance_different_color() {
+        let a = TexelBuilder::new('x').fg(2).build();
+
+        assert!(!a.same_appearance(&TexelBuilder::new('x').fg(3).build()));
+        assert!(!a.same_appearance(&TexelBuilder::new('x').fg(2).bg(1).build()));
+        assert!(!a.same_appearance(&TexelBuilder::new('y').fg(2).build()));
+    }
+}